@@ -6,55 +6,176 @@
 //! # Authentication Module
 //!
 //! This module provides JWT-based authentication functionality including:
-//! - Token creation and verification
+//! - Access token creation and verification
+//! - Opaque refresh token generation and hashing
 //! - Request authentication via the `AuthUser` extractor
 //!
 //! ## Security Notes
-//! - Tokens expire after 24 hours
-//! - The JWT secret should be set via the `JWT_SECRET` environment variable
+//! - Access tokens expire after `ACCESS_TOKEN_TTL_MINUTES` minutes
+//! - Refresh tokens are long-lived, opaque, and persisted (hashed) in the
+//!   `refresh_tokens` table; see `handlers::auth` for issuance and rotation
+//! - The JWT secret comes from `Config::jwt_secret` (see `config.rs`);
+//!   `init_jwt_secret` must be called once at startup before any token is
+//!   created or verified
 
 use jsonwebtoken::{decode, encode, DecodingKey, EncodingKey, Header, Validation};
 use serde::{Deserialize, Serialize};
-use std::env;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::sync::{Arc, OnceLock};
+use uuid::Uuid;
 use axum::{
-    extract::FromRequestParts,
+    extract::{FromRef, FromRequestParts, Path},
     http::{request::Parts, StatusCode},
 };
 
-/// JWT claims structure containing user identification and expiration.
+use crate::DbPool;
+
+/// Lifetime of an access token, in minutes.
+pub const ACCESS_TOKEN_TTL_MINUTES: i64 = 15;
+
+/// Lifetime of a refresh token, in days.
+pub const REFRESH_TOKEN_TTL_DAYS: i64 = 30;
+
+/// Lifetime of a budget share link, in days.
+pub const SHARE_TOKEN_TTL_DAYS: i64 = 7;
+
+static JWT_SECRET: OnceLock<String> = OnceLock::new();
+
+/// Stores the secret used to sign and verify JWTs. Call once at startup,
+/// after loading `Config`, before any request touches `AuthUser` or the
+/// token functions below.
+pub fn init_jwt_secret(secret: String) {
+    JWT_SECRET.set(secret).expect("init_jwt_secret called more than once");
+}
+
+/// Returns the configured JWT secret.
+///
+/// # Panics
+/// If [`init_jwt_secret`] hasn't been called yet.
+fn jwt_secret() -> &'static str {
+    JWT_SECRET
+        .get()
+        .expect("JWT secret not initialized; call auth::init_jwt_secret at startup")
+}
+
+/// Scope value required on a valid share token, guarding against a
+/// `ShareClaims` token being reused as (or confused for) a regular access
+/// token or vice versa.
+const SHARE_SCOPE: &str = "share";
+
+/// JWT claims structure containing user identification, global role, and expiration.
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Claims {
     /// Subject - the user ID
     pub sub: String,
+    /// The user's global site role (e.g. "user" or "admin")
+    pub role: String,
     /// Expiration timestamp (Unix epoch)
     pub exp: usize,
 }
 
-/// Creates a new JWT token for the specified user.
+/// Creates a new short-lived access token for the specified user.
 ///
 /// # Arguments
 ///
 /// * `user_id` - The unique identifier of the user
+/// * `role` - The user's global site role, embedded in the token so handlers
+///   can authorize site-level actions without a second database round trip
 ///
 /// # Returns
 ///
-/// A `Result` containing the encoded JWT string on success, or a JWT error on failure.
+/// A `Result` containing the encoded JWT string and its `expires_in` in
+/// seconds on success, or a JWT error on failure.
 ///
 /// # Example
 ///
 /// ```ignore
-/// let token = create_token("user-uuid-here")?;
+/// let (token, expires_in) = create_access_token("user-uuid-here", "user")?;
 /// ```
-pub fn create_token(user_id: &str) -> Result<String, jsonwebtoken::errors::Error> {
-    let secret = env::var("JWT_SECRET").unwrap_or_else(|_| "secret".to_string());
+pub fn create_access_token(user_id: &str, role: &str) -> Result<(String, i64), jsonwebtoken::errors::Error> {
+    let secret = jwt_secret();
 
+    let ttl = chrono::Duration::minutes(ACCESS_TOKEN_TTL_MINUTES);
     let expiration = chrono::Utc::now()
-        .checked_add_signed(chrono::Duration::hours(24))
+        .checked_add_signed(ttl)
         .expect("valid timestamp")
         .timestamp();
 
     let claims = Claims {
         sub: user_id.to_string(),
+        role: role.to_string(),
+        exp: expiration as usize,
+    };
+
+    let token = encode(
+        &Header::default(),
+        &claims,
+        &EncodingKey::from_secret(secret.as_bytes()),
+    )?;
+
+    Ok((token, ttl.num_seconds()))
+}
+
+/// Generates a new opaque refresh token and its `jti`.
+///
+/// The returned token is high-entropy and unguessable; only its hash (see
+/// [`hash_refresh_token`]) should ever be persisted.
+///
+/// # Returns
+///
+/// A tuple of `(raw_token, jti)`.
+pub fn generate_refresh_token() -> (String, String) {
+    let token = format!("{}{}", Uuid::new_v4().simple(), Uuid::new_v4().simple());
+    let jti = Uuid::new_v4().to_string();
+    (token, jti)
+}
+
+/// Hashes a raw refresh token for storage/lookup.
+///
+/// Refresh tokens are already high-entropy random values, so a fast
+/// cryptographic hash (rather than a slow password hash) is sufficient to
+/// keep the raw token out of the database.
+pub fn hash_refresh_token(token: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(token.as_bytes());
+    hasher
+        .finalize()
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+/// Claims carried by a read-only budget share link. Distinct from [`Claims`]
+/// so a share token can never be mistaken for a regular access token: it
+/// identifies a budget rather than a user, and callers must check `scope`.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ShareClaims {
+    /// The budget this link grants read-only access to
+    pub budget_id: String,
+    /// Always `"share"`; distinguishes this token from a regular access token
+    pub scope: String,
+    /// Expiration timestamp (Unix epoch)
+    pub exp: usize,
+}
+
+/// Mints a short-lived signed token granting read-only access to a single
+/// budget's snapshot, with no association to any user account.
+///
+/// # Returns
+///
+/// A `Result` containing the encoded token on success, or a JWT error on failure.
+pub fn create_share_token(budget_id: &str) -> Result<String, jsonwebtoken::errors::Error> {
+    let secret = jwt_secret();
+
+    let expiration = chrono::Utc::now()
+        .checked_add_signed(chrono::Duration::days(SHARE_TOKEN_TTL_DAYS))
+        .expect("valid timestamp")
+        .timestamp();
+
+    let claims = ShareClaims {
+        budget_id: budget_id.to_string(),
+        scope: SHARE_SCOPE.to_string(),
         exp: expiration as usize,
     };
 
@@ -65,6 +186,24 @@ pub fn create_token(user_id: &str) -> Result<String, jsonwebtoken::errors::Error
     )
 }
 
+/// Verifies a share token and extracts its claims, rejecting expired tokens
+/// or tokens whose `scope` isn't `"share"`.
+pub fn verify_share_token(token: &str) -> Result<ShareClaims, jsonwebtoken::errors::Error> {
+    let secret = jwt_secret();
+
+    let token_data = decode::<ShareClaims>(
+        token,
+        &DecodingKey::from_secret(secret.as_bytes()),
+        &Validation::default(),
+    )?;
+
+    if token_data.claims.scope != SHARE_SCOPE {
+        return Err(jsonwebtoken::errors::ErrorKind::InvalidToken.into());
+    }
+
+    Ok(token_data.claims)
+}
+
 /// Verifies a JWT token and extracts its claims.
 ///
 /// # Arguments
@@ -75,7 +214,7 @@ pub fn create_token(user_id: &str) -> Result<String, jsonwebtoken::errors::Error
 ///
 /// A `Result` containing the decoded `Claims` on success, or a JWT error on failure.
 pub fn verify_token(token: &str) -> Result<Claims, jsonwebtoken::errors::Error> {
-    let secret = env::var("JWT_SECRET").unwrap_or_else(|_| "secret".to_string());
+    let secret = jwt_secret();
 
     let token_data = decode::<Claims>(
         token,
@@ -89,8 +228,10 @@ pub fn verify_token(token: &str) -> Result<Claims, jsonwebtoken::errors::Error>
 /// Authenticated user extractor for Axum handlers.
 ///
 /// This struct can be used as an extractor in Axum route handlers to require
-/// authentication. It automatically validates the `Authorization` header and
-/// extracts the user ID from the JWT token.
+/// authentication. It automatically validates the `Authorization` header,
+/// extracts the user ID and role from the JWT token, and re-checks the
+/// account's `status` against the database so a token belonging to a
+/// since-blocked account is rejected even if it hasn't expired yet.
 ///
 /// # Example
 ///
@@ -102,35 +243,137 @@ pub fn verify_token(token: &str) -> Result<Claims, jsonwebtoken::errors::Error>
 pub struct AuthUser {
     /// The authenticated user's unique identifier
     pub user_id: String,
+    /// The authenticated user's global site role
+    pub role: String,
+}
+
+/// Extracts the bearer token from the `Authorization` header and decodes it
+/// into `Claims`, shared by `AuthUser` and `BudgetMembership`.
+fn extract_claims(parts: &Parts) -> Result<Claims, StatusCode> {
+    let auth_header = parts
+        .headers
+        .get("Authorization")
+        .and_then(|h| h.to_str().ok())
+        .ok_or(StatusCode::UNAUTHORIZED)?;
+
+    if !auth_header.starts_with("Bearer ") {
+        return Err(StatusCode::UNAUTHORIZED);
+    }
+
+    let token = &auth_header[7..];
+
+    verify_token(token).map_err(|_| StatusCode::UNAUTHORIZED)
 }
 
 #[axum::async_trait]
 impl<S> FromRequestParts<S> for AuthUser
 where
     S: Send + Sync,
+    Arc<DbPool>: FromRef<S>,
 {
     type Rejection = StatusCode;
 
-    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
-        // Retrieve the Authorization header
-        let auth_header = parts
-            .headers
-            .get("Authorization")
-            .and_then(|h| h.to_str().ok())
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let claims = extract_claims(parts)?;
+        let pool = Arc::<DbPool>::from_ref(state);
+
+        let status = sqlx::query_scalar::<_, String>("SELECT status FROM users WHERE id = ?")
+            .bind(&claims.sub)
+            .fetch_optional(pool.as_ref())
+            .await
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
             .ok_or(StatusCode::UNAUTHORIZED)?;
 
-        // Verify the "Bearer TOKEN" format
-        if !auth_header.starts_with("Bearer ") {
-            return Err(StatusCode::UNAUTHORIZED);
+        if status == "blocked" {
+            return Err(StatusCode::FORBIDDEN);
         }
 
-        let token = &auth_header[7..];
+        Ok(AuthUser {
+            user_id: claims.sub,
+            role: claims.role,
+        })
+    }
+}
 
-        // Verify the token
-        let claims = verify_token(token).map_err(|_| StatusCode::UNAUTHORIZED)?;
+/// Ranks budget membership roles so callers can require "at least" a role
+/// instead of matching an exact string. Unknown roles rank lowest.
+fn role_rank(role: &str) -> u8 {
+    match role {
+        "owner" => 3,
+        "admin" => 2,
+        "member" => 1,
+        _ => 0,
+    }
+}
 
-        Ok(AuthUser {
+/// The authenticated user's membership in a specific budget, extracted from
+/// the `:budget_id` path parameter.
+///
+/// Replaces the ad-hoc `SELECT COUNT(*) FROM budget_members WHERE ...`
+/// checks that used to be duplicated across handlers: pulling this extractor
+/// into a handler's signature guarantees the caller is a member of the
+/// budget named by its `:budget_id` path parameter, and `require_role` lets
+/// it additionally assert a minimum role (owner > admin > member).
+pub struct BudgetMembership {
+    /// The authenticated user's unique identifier
+    pub user_id: String,
+    /// The user's role within the budget: "owner", "admin", or "member"
+    pub role: String,
+}
+
+impl BudgetMembership {
+    /// Rejects with `403 Forbidden` unless this membership's role is at
+    /// least as privileged as `minimum`.
+    pub fn require_role(&self, minimum: &str) -> Result<(), StatusCode> {
+        if role_rank(&self.role) >= role_rank(minimum) {
+            Ok(())
+        } else {
+            Err(StatusCode::FORBIDDEN)
+        }
+    }
+}
+
+#[axum::async_trait]
+impl<S> FromRequestParts<S> for BudgetMembership
+where
+    S: Send + Sync,
+    Arc<DbPool>: FromRef<S>,
+{
+    type Rejection = StatusCode;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let claims = extract_claims(parts)?;
+        let pool = Arc::<DbPool>::from_ref(state);
+
+        let status = sqlx::query_scalar::<_, String>("SELECT status FROM users WHERE id = ?")
+            .bind(&claims.sub)
+            .fetch_optional(pool.as_ref())
+            .await
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+            .ok_or(StatusCode::UNAUTHORIZED)?;
+
+        if status == "blocked" {
+            return Err(StatusCode::FORBIDDEN);
+        }
+
+        let Path(params) = Path::<HashMap<String, String>>::from_request_parts(parts, state)
+            .await
+            .map_err(|_| StatusCode::BAD_REQUEST)?;
+        let budget_id = params.get("budget_id").ok_or(StatusCode::BAD_REQUEST)?;
+
+        let role = sqlx::query_scalar::<_, String>(
+            "SELECT role FROM budget_members WHERE budget_id = ? AND user_id = ?"
+        )
+            .bind(budget_id)
+            .bind(&claims.sub)
+            .fetch_optional(pool.as_ref())
+            .await
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+            .ok_or(StatusCode::FORBIDDEN)?;
+
+        Ok(BudgetMembership {
             user_id: claims.sub,
+            role,
         })
     }
 }
\ No newline at end of file
@@ -0,0 +1,95 @@
+// Copyright (c) 2024 Tempo Budget
+// SPDX-License-Identifier: MIT
+//
+// Evaluation of budget spending thresholds.
+
+//! # Budget Alert Evaluation
+//!
+//! `budget_alerts` rows are user-configured spending thresholds (see
+//! `handlers::budget_alerts` for the CRUD endpoints); this module is what
+//! actually checks them. [`evaluate`] recomputes the current period's spend
+//! for a budget (and, for category-scoped alerts, a category) and flips any
+//! untriggered alert whose threshold that spend now meets or exceeds,
+//! publishing a `BudgetEvent::AlertTriggered` for each one so subscribers
+//! find out without polling.
+
+use crate::{
+    events::{BudgetEvent, EventRegistry},
+    models::BudgetAlert,
+    DbPool,
+};
+
+/// Recomputes this month's expense total for `budget_id` (and, for
+/// category-scoped alerts, `category_id`) and triggers any alert whose
+/// threshold is now met. Called after `create_transaction` commits an
+/// expense; alerts only track spend, so callers should skip this for
+/// income.
+pub async fn evaluate(
+    pool: &DbPool,
+    events: &EventRegistry,
+    budget_id: &str,
+    category_id: &str,
+) -> Result<(), sqlx::Error> {
+    let alerts = sqlx::query_as::<_, BudgetAlert>(
+        "SELECT id, budget_id, category_id, threshold_type, threshold_value, limit_amount,
+         period, triggered, triggered_at, created_at
+         FROM budget_alerts
+         WHERE budget_id = ? AND triggered = 0 AND (category_id IS NULL OR category_id = ?)"
+    )
+        .bind(budget_id)
+        .bind(category_id)
+        .fetch_all(pool)
+        .await?;
+
+    for alert in alerts {
+        let total = current_period_total(pool, budget_id, alert.category_id.as_deref()).await?;
+
+        let required = match alert.threshold_type.as_str() {
+            "percentage" => alert.limit_amount.unwrap_or(0.0) * alert.threshold_value / 100.0,
+            _ => alert.threshold_value,
+        };
+
+        if required <= 0.0 || total < required {
+            continue;
+        }
+
+        let triggered_at = chrono::Utc::now().to_rfc3339();
+
+        sqlx::query("UPDATE budget_alerts SET triggered = 1, triggered_at = ? WHERE id = ?")
+            .bind(&triggered_at)
+            .bind(&alert.id)
+            .execute(pool)
+            .await?;
+
+        events.publish(budget_id, BudgetEvent::AlertTriggered {
+            alert_id: alert.id,
+            category_id: alert.category_id,
+            threshold_type: alert.threshold_type,
+            threshold_value: alert.threshold_value,
+            current_total: total,
+        });
+    }
+
+    Ok(())
+}
+
+/// Sums expense transactions for the current calendar month, optionally
+/// scoped to a single category. Budget alerts only support a "monthly"
+/// period today.
+async fn current_period_total(
+    pool: &DbPool,
+    budget_id: &str,
+    category_id: Option<&str>,
+) -> Result<f64, sqlx::Error> {
+    sqlx::query_scalar::<_, f64>(
+        "SELECT COALESCE(SUM(amount), 0) FROM transactions
+         WHERE budget_id = ? AND transaction_type = 'expense'
+           AND strftime('%Y-%m', date) = strftime('%Y-%m', 'now')
+           AND (? IS NULL OR category_id = ?)"
+    )
+        .bind(budget_id)
+        .bind(category_id)
+        .bind(category_id)
+        .fetch_one(pool)
+        .await
+}
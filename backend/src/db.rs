@@ -0,0 +1,46 @@
+// Copyright (c) 2024 Tempo Budget
+// SPDX-License-Identifier: MIT
+//
+// Transaction-wrapping helper for multi-step handlers.
+
+//! # Database Module
+//!
+//! Several handlers issue more than one statement to do their job — an
+//! `INSERT` followed by the `SELECT` that returns the created row, or a
+//! cascading delete across related tables — with no transactional
+//! guarantee tying them together. A crash (or another request racing in)
+//! between those statements used to leave inconsistent state: a row
+//! inserted but never returned, or a parent deleted while its children
+//! survive. [`with_transaction`] begins a `sqlx` transaction, hands the
+//! caller a `&mut Transaction` to issue queries against, and commits on
+//! `Ok` or rolls back on `Err`, so callers get atomicity without managing
+//! `begin`/`commit`/`rollback` themselves.
+
+use std::future::Future;
+use sqlx::{Sqlite, Transaction};
+
+use crate::DbPool;
+
+/// Runs `body` against a single `sqlx` transaction checked out from `pool`,
+/// committing if it resolves to `Ok` and rolling back otherwise. The
+/// rollback on a lost connection (where `tx.rollback()` itself fails) is
+/// best-effort and silently ignored, since `body`'s error is what matters
+/// to the caller.
+pub async fn with_transaction<T, F, Fut>(pool: &DbPool, body: F) -> Result<T, sqlx::Error>
+where
+    F: FnOnce(&mut Transaction<'static, Sqlite>) -> Fut,
+    Fut: Future<Output = Result<T, sqlx::Error>>,
+{
+    let mut tx = pool.begin().await?;
+
+    match body(&mut tx).await {
+        Ok(value) => {
+            tx.commit().await?;
+            Ok(value)
+        }
+        Err(e) => {
+            let _ = tx.rollback().await;
+            Err(e)
+        }
+    }
+}
@@ -0,0 +1,61 @@
+// Copyright (c) 2024 Tempo Budget
+// SPDX-License-Identifier: MIT
+//
+// Shared application error type for HTTP handlers.
+
+//! # Error Module
+//!
+//! A typed alternative to returning a bare `StatusCode` from handlers. The
+//! main benefit is [`AppError`]'s `From<sqlx::Error>` impl: it inspects
+//! `sqlx::Error::Database` for a unique-constraint violation and maps it to
+//! `409 Conflict` instead of collapsing every database error to `500`,
+//! which used to hide real conflicts (a duplicate invitation, a duplicate
+//! membership) behind an opaque server error.
+
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use serde::Serialize;
+
+/// A typed error a handler can return, convertible to an HTTP response via
+/// `IntoResponse`.
+#[derive(Debug)]
+pub enum AppError {
+    NotFound,
+    Forbidden,
+    BadRequest(String),
+    Conflict(String),
+    Internal,
+}
+
+#[derive(Serialize)]
+struct ErrorBody {
+    error: String,
+}
+
+impl IntoResponse for AppError {
+    fn into_response(self) -> Response {
+        let (status, message) = match self {
+            AppError::NotFound => (StatusCode::NOT_FOUND, "not found".to_string()),
+            AppError::Forbidden => (StatusCode::FORBIDDEN, "forbidden".to_string()),
+            AppError::BadRequest(message) => (StatusCode::BAD_REQUEST, message),
+            AppError::Conflict(message) => (StatusCode::CONFLICT, message),
+            AppError::Internal => (StatusCode::INTERNAL_SERVER_ERROR, "internal server error".to_string()),
+        };
+
+        (status, Json(ErrorBody { error: message })).into_response()
+    }
+}
+
+impl From<sqlx::Error> for AppError {
+    fn from(err: sqlx::Error) -> Self {
+        if let sqlx::Error::Database(db_err) = &err {
+            if db_err.is_unique_violation() {
+                return AppError::Conflict("a matching record already exists".to_string());
+            }
+        }
+
+        tracing::error!("Unhandled database error: {:?}", err);
+        AppError::Internal
+    }
+}
@@ -0,0 +1,71 @@
+// Copyright (c) 2024 Tempo Budget
+// SPDX-License-Identifier: MIT
+//
+// In-process event broadcasting for real-time group-budget updates.
+
+//! # Events Module
+//!
+//! Group budgets are collaborative, so clients subscribe to a live event
+//! stream (see `handlers::events`) instead of polling. This module holds the
+//! broadcast registry those streams read from: one `tokio::sync::broadcast`
+//! channel per budget, created lazily on first subscribe or publish.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use serde::Serialize;
+use tokio::sync::broadcast;
+
+/// Channel capacity for a single budget's event stream. A lagging
+/// subscriber drops the oldest buffered events rather than blocking
+/// publishers.
+const CHANNEL_CAPACITY: usize = 64;
+
+/// A typed event published to a budget's subscribers.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type")]
+pub enum BudgetEvent {
+    /// A user accepted an invitation and joined the budget
+    MemberJoined { user_id: String, role: String },
+    /// A member was removed from the budget
+    MemberRemoved { user_id: String },
+    /// The budget's own fields (name, active status, etc.) changed
+    BudgetUpdated,
+    /// A budget alert's spending threshold was just crossed
+    AlertTriggered {
+        alert_id: String,
+        category_id: Option<String>,
+        threshold_type: String,
+        threshold_value: f64,
+        current_total: f64,
+    },
+}
+
+/// Registry of per-budget broadcast channels, shared across the
+/// application via `AppState`.
+#[derive(Clone, Default)]
+pub struct EventRegistry {
+    channels: Arc<Mutex<HashMap<String, broadcast::Sender<BudgetEvent>>>>,
+}
+
+impl EventRegistry {
+    /// Subscribes to a budget's event stream, creating its channel if this
+    /// is the first subscriber.
+    pub fn subscribe(&self, budget_id: &str) -> broadcast::Receiver<BudgetEvent> {
+        let mut channels = self.channels.lock().expect("event registry mutex poisoned");
+        channels
+            .entry(budget_id.to_string())
+            .or_insert_with(|| broadcast::channel(CHANNEL_CAPACITY).0)
+            .subscribe()
+    }
+
+    /// Publishes an event to a budget's subscribers. A no-op if nobody is
+    /// currently subscribed.
+    pub fn publish(&self, budget_id: &str, event: BudgetEvent) {
+        let mut channels = self.channels.lock().expect("event registry mutex poisoned");
+        let sender = channels
+            .entry(budget_id.to_string())
+            .or_insert_with(|| broadcast::channel(CHANNEL_CAPACITY).0);
+        // Err means no receivers are subscribed right now; nothing to do.
+        let _ = sender.send(event);
+    }
+}
@@ -0,0 +1,287 @@
+// Copyright (c) 2024 Tempo Budget
+// SPDX-License-Identifier: MIT
+//
+// Receipt attachment HTTP handlers.
+
+//! # Attachment Handlers
+//!
+//! This module provides HTTP handlers for receipt attachments on
+//! transactions:
+//! - `POST /api/transactions/:id/attachments` - Upload a receipt (multipart)
+//! - `GET /api/transactions/:id/attachments` - List a transaction's receipts
+//! - `DELETE /api/attachments/:id` - Delete a receipt
+//!
+//! File content is held behind the `Storage` trait (see `storage.rs`), so
+//! these handlers only ever deal in opaque `storage_key`s, not paths or S3
+//! object details.
+
+use axum::{
+    extract::{Multipart, Path, State},
+    http::StatusCode,
+    Json,
+};
+use sqlx::{Sqlite, Transaction};
+use std::sync::Arc;
+use uuid::Uuid;
+use chrono::Utc;
+
+use crate::{auth::AuthUser, models::Attachment, storage::Storage, DbPool};
+
+/// Returns `Ok(())` iff `user_id` is a member of `budget_id`, mirroring
+/// `auth::BudgetMembership`'s check by hand: that extractor reads a path
+/// parameter literally named `budget_id`, but these routes are keyed by a
+/// transaction or attachment id instead, so callers resolve `budget_id`
+/// themselves (see `transaction_budget_id`) and check membership with this
+/// helper before touching storage.
+async fn require_membership(pool: &DbPool, budget_id: &str, user_id: &str) -> Result<(), StatusCode> {
+    let is_member = sqlx::query_scalar::<_, i64>(
+        "SELECT COUNT(*) FROM budget_members WHERE budget_id = ? AND user_id = ?"
+    )
+        .bind(budget_id)
+        .bind(user_id)
+        .fetch_one(pool)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    if is_member > 0 {
+        Ok(())
+    } else {
+        Err(StatusCode::FORBIDDEN)
+    }
+}
+
+/// Looks up the budget a transaction belongs to, for membership checks.
+async fn transaction_budget_id(pool: &DbPool, transaction_id: &str) -> Result<Option<String>, StatusCode> {
+    sqlx::query_scalar::<_, String>("SELECT budget_id FROM transactions WHERE id = ?")
+        .bind(transaction_id)
+        .fetch_optional(pool)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)
+}
+
+/// Uploads a receipt file for a transaction.
+///
+/// # Endpoint
+/// `POST /api/transactions/:id/attachments`
+///
+/// # Authentication
+/// Requires a valid JWT token. User must be a member of the transaction's
+/// budget.
+///
+/// # Path Parameters
+/// - `id`: The transaction's unique identifier
+///
+/// # Request Body
+/// `multipart/form-data` with a single `file` part carrying the receipt
+/// (image or PDF).
+///
+/// # Returns
+/// - `200 OK` with the created `Attachment` object
+/// - `400 Bad Request` if the request has no `file` part
+/// - `403 Forbidden` if the user is not a member of the transaction's budget
+/// - `404 Not Found` if the transaction doesn't exist
+/// - `500 Internal Server Error` if the upload or insert fails
+pub async fn upload_attachment(
+    State(pool): State<Arc<DbPool>>,
+    State(storage): State<Arc<dyn Storage>>,
+    auth: AuthUser,
+    Path(transaction_id): Path<String>,
+    mut multipart: Multipart,
+) -> Result<Json<Attachment>, StatusCode> {
+    let budget_id = transaction_budget_id(&pool, &transaction_id).await?.ok_or(StatusCode::NOT_FOUND)?;
+    require_membership(&pool, &budget_id, &auth.user_id).await?;
+
+    let field = multipart
+        .next_field()
+        .await
+        .map_err(|_| StatusCode::BAD_REQUEST)?
+        .ok_or(StatusCode::BAD_REQUEST)?;
+
+    if field.name() != Some("file") {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    let filename = field.file_name().unwrap_or("receipt").to_string();
+    let content_type = field.content_type().unwrap_or("application/octet-stream").to_string();
+    let content = field.bytes().await.map_err(|_| StatusCode::BAD_REQUEST)?;
+    let size = content.len() as i64;
+
+    let id = Uuid::new_v4().to_string();
+    // Opaque key: never derive this from client-supplied bytes (e.g. the
+    // upload filename), or a crafted `../../..` filename could escape the
+    // storage backend's base directory. The original filename is kept only
+    // in the `filename` DB column for display/download purposes.
+    let storage_key = format!("{transaction_id}/{id}");
+    let now = Utc::now().to_rfc3339();
+
+    storage
+        .put(&storage_key, &content_type, content.to_vec())
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to store attachment: {}", e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    sqlx::query(
+        "INSERT INTO attachments (id, transaction_id, filename, content_type, storage_key, size, created_at)
+         VALUES (?, ?, ?, ?, ?, ?, ?)"
+    )
+        .bind(&id)
+        .bind(&transaction_id)
+        .bind(&filename)
+        .bind(&content_type)
+        .bind(&storage_key)
+        .bind(size)
+        .bind(&now)
+        .execute(pool.as_ref())
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let attachment = sqlx::query_as::<_, Attachment>(
+        "SELECT id, transaction_id, filename, content_type, storage_key, size, created_at
+         FROM attachments WHERE id = ?"
+    )
+        .bind(&id)
+        .fetch_one(pool.as_ref())
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(Json(attachment))
+}
+
+/// Lists the receipts attached to a transaction.
+///
+/// # Endpoint
+/// `GET /api/transactions/:id/attachments`
+///
+/// # Authentication
+/// Requires a valid JWT token. User must be a member of the transaction's
+/// budget.
+///
+/// # Path Parameters
+/// - `id`: The transaction's unique identifier
+///
+/// # Returns
+/// - `200 OK` with array of `Attachment` objects
+/// - `403 Forbidden` if the user is not a member of the transaction's budget
+/// - `404 Not Found` if the transaction doesn't exist
+/// - `500 Internal Server Error` if the query fails
+pub async fn get_attachments(
+    State(pool): State<Arc<DbPool>>,
+    auth: AuthUser,
+    Path(transaction_id): Path<String>,
+) -> Result<Json<Vec<Attachment>>, StatusCode> {
+    let budget_id = transaction_budget_id(&pool, &transaction_id).await?.ok_or(StatusCode::NOT_FOUND)?;
+    require_membership(&pool, &budget_id, &auth.user_id).await?;
+
+    let attachments = sqlx::query_as::<_, Attachment>(
+        "SELECT id, transaction_id, filename, content_type, storage_key, size, created_at
+         FROM attachments WHERE transaction_id = ? ORDER BY created_at DESC"
+    )
+        .bind(&transaction_id)
+        .fetch_all(pool.as_ref())
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(Json(attachments))
+}
+
+/// Deletes a receipt attachment, removing both its database row and its
+/// stored file content.
+///
+/// # Endpoint
+/// `DELETE /api/attachments/:id`
+///
+/// # Authentication
+/// Requires a valid JWT token. User must be a member of the owning
+/// transaction's budget.
+///
+/// # Path Parameters
+/// - `id`: The attachment's unique identifier
+///
+/// # Returns
+/// - `204 No Content` on successful deletion
+/// - `403 Forbidden` if the user is not a member of the owning budget
+/// - `404 Not Found` if the attachment doesn't exist
+/// - `500 Internal Server Error` if deletion fails
+pub async fn delete_attachment(
+    State(pool): State<Arc<DbPool>>,
+    State(storage): State<Arc<dyn Storage>>,
+    auth: AuthUser,
+    Path(id): Path<String>,
+) -> Result<StatusCode, StatusCode> {
+    let (storage_key, budget_id) = sqlx::query_as::<_, (String, String)>(
+        "SELECT a.storage_key, t.budget_id FROM attachments a
+         JOIN transactions t ON t.id = a.transaction_id
+         WHERE a.id = ?"
+    )
+        .bind(&id)
+        .fetch_optional(pool.as_ref())
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    require_membership(&pool, &budget_id, &auth.user_id).await?;
+
+    storage.delete(&storage_key).await.map_err(|e| {
+        tracing::error!("Failed to delete stored attachment: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    sqlx::query("DELETE FROM attachments WHERE id = ?")
+        .bind(&id)
+        .execute(pool.as_ref())
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Deletes the stored file content of every attachment belonging to
+/// `transaction_id`. Called by
+/// `handlers::transactions::delete_transaction` so a deleted transaction
+/// doesn't leave orphaned receipt files behind.
+///
+/// File content lives outside the database, so it can't be rolled back the
+/// way a `sqlx` transaction can; a storage error is logged and otherwise
+/// ignored rather than failing the whole delete (unlike
+/// [`delete_attachment`], which fails with `500` on a storage error). The database row
+/// deletion, on the other hand, is left to the caller's transaction (see
+/// `delete_attachment_rows_for_transaction`) so it commits or rolls back
+/// atomically with the rest of the cascade.
+pub async fn delete_attachments_for_transaction(
+    pool: &DbPool,
+    storage: &dyn Storage,
+    transaction_id: &str,
+) -> Result<(), StatusCode> {
+    let storage_keys = sqlx::query_scalar::<_, String>(
+        "SELECT storage_key FROM attachments WHERE transaction_id = ?"
+    )
+        .bind(transaction_id)
+        .fetch_all(pool)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    for key in storage_keys {
+        if let Err(e) = storage.delete(&key).await {
+            tracing::error!("Failed to delete stored attachment {}: {}", key, e);
+        }
+    }
+
+    Ok(())
+}
+
+/// Deletes the `attachments` rows for `transaction_id` against an
+/// already-open transaction, so callers can commit it alongside the
+/// deletion of the transaction row itself.
+pub async fn delete_attachment_rows_for_transaction(
+    tx: &mut Transaction<'static, Sqlite>,
+    transaction_id: &str,
+) -> Result<(), sqlx::Error> {
+    sqlx::query("DELETE FROM attachments WHERE transaction_id = ?")
+        .bind(transaction_id)
+        .execute(&mut **tx)
+        .await?;
+
+    Ok(())
+}
@@ -7,7 +7,9 @@
 //!
 //! This module provides HTTP handlers for user authentication:
 //! - `POST /api/auth/register` - Create a new user account
-//! - `POST /api/auth/login` - Authenticate and receive a JWT token
+//! - `POST /api/auth/login` - Authenticate and receive a token pair
+//! - `POST /api/auth/refresh` - Exchange a refresh token for a new token pair
+//! - `POST /api/auth/logout` - Revoke a refresh token
 
 use axum::{
     extract::State,
@@ -16,15 +18,49 @@ use axum::{
 };
 use std::sync::Arc;
 use uuid::Uuid;
-use chrono::Utc;
+use chrono::{Duration, Utc};
 use utoipa;
 
 use crate::{
-    auth::create_token,
-    models::{AuthResponse, CreateUser, LoginRequest, User},
+    auth::{create_access_token, generate_refresh_token, hash_refresh_token, REFRESH_TOKEN_TTL_DAYS},
+    models::{AuthResponse, CreateUser, LoginRequest, LogoutRequest, RefreshRequest, User},
+    password,
     DbPool,
 };
 
+/// Issues a new access/refresh token pair for a user and persists the
+/// refresh token (hashed) in the `refresh_tokens` table.
+async fn issue_tokens(pool: &DbPool, user_id: &str, role: &str) -> Result<(String, String, i64), StatusCode> {
+    let (access_token, expires_in) =
+        create_access_token(user_id, role).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let (refresh_token, jti) = generate_refresh_token();
+    let token_hash = hash_refresh_token(&refresh_token);
+
+    let id = Uuid::new_v4().to_string();
+    let now = Utc::now();
+    let expires_at = (now + Duration::days(REFRESH_TOKEN_TTL_DAYS)).to_rfc3339();
+
+    sqlx::query(
+        "INSERT INTO refresh_tokens (id, user_id, token_hash, jti, expires_at, revoked, created_at)
+         VALUES (?, ?, ?, ?, ?, 0, ?)"
+    )
+        .bind(&id)
+        .bind(user_id)
+        .bind(&token_hash)
+        .bind(&jti)
+        .bind(&expires_at)
+        .bind(now.to_rfc3339())
+        .execute(pool)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to persist refresh token: {:?}", e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    Ok((access_token, refresh_token, expires_in))
+}
+
 /// Registers a new user account.
 ///
 /// # Endpoint
@@ -36,7 +72,7 @@ use crate::{
 /// - `password`: Plain-text password (will be hashed)
 ///
 /// # Returns
-/// - `200 OK` with `AuthResponse` containing JWT token and user details
+/// - `200 OK` with `AuthResponse` containing an access/refresh token pair and user details
 /// - `500 Internal Server Error` if registration fails
 #[utoipa::path(
     post,
@@ -52,8 +88,8 @@ pub async fn register(
     State(pool): State<Arc<DbPool>>,
     Json(payload): Json<CreateUser>,
 ) -> Result<Json<AuthResponse>, StatusCode> {
-    // Hash the password
-    let password_hash = bcrypt::hash(&payload.password, bcrypt::DEFAULT_COST)
+    // Hash the password (always Argon2id for new accounts)
+    let password_hash = password::hash(&payload.password)
         .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
 
     let id = Uuid::new_v4().to_string();
@@ -76,7 +112,7 @@ pub async fn register(
 
     // Retrieve the created user
     let user = sqlx::query_as::<_, User>(
-        "SELECT id, email, name, password_hash, avatar, phone, created_at, updated_at
+        "SELECT id, email, name, password_hash, avatar, phone, role, status, created_at, updated_at
          FROM users WHERE id = ?"
     )
         .bind(&id)
@@ -84,13 +120,12 @@ pub async fn register(
         .await
         .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
 
-    // Generate the JWT token
-    let token = create_token(&id).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let (access_token, refresh_token, expires_in) = issue_tokens(&pool, &id, &user.role).await?;
 
-    Ok(Json(AuthResponse { token, user }))
+    Ok(Json(AuthResponse { access_token, refresh_token, expires_in, user }))
 }
 
-/// Authenticates a user and returns a JWT token.
+/// Authenticates a user and returns an access/refresh token pair.
 ///
 /// # Endpoint
 /// `POST /api/auth/login`
@@ -100,8 +135,9 @@ pub async fn register(
 /// - `password`: User's plain-text password
 ///
 /// # Returns
-/// - `200 OK` with `AuthResponse` containing JWT token and user details
+/// - `200 OK` with `AuthResponse` containing an access/refresh token pair and user details
 /// - `401 Unauthorized` if credentials are invalid
+/// - `403 Forbidden` if the account has been blocked
 /// - `500 Internal Server Error` if authentication fails
 #[utoipa::path(
     post,
@@ -111,6 +147,7 @@ pub async fn register(
     responses(
         (status = 200, description = "Login successful", body = AuthResponse),
         (status = 401, description = "Invalid credentials"),
+        (status = 403, description = "Account is blocked"),
         (status = 500, description = "Authentication failed")
     )
 )]
@@ -120,7 +157,7 @@ pub async fn login(
 ) -> Result<Json<AuthResponse>, StatusCode> {
     // Find the user by email
     let user = sqlx::query_as::<_, User>(
-        "SELECT id, email, name, password_hash, avatar, phone, created_at, updated_at
+        "SELECT id, email, name, password_hash, avatar, phone, role, status, created_at, updated_at
          FROM users WHERE email = ?"
     )
         .bind(&payload.email)
@@ -128,16 +165,132 @@ pub async fn login(
         .await
         .map_err(|_| StatusCode::UNAUTHORIZED)?;
 
-    // Verify the password
-    let valid = bcrypt::verify(&payload.password, &user.password_hash)
+    // Verify the password (supports both legacy bcrypt and current Argon2id hashes)
+    let valid = password::verify(&payload.password, &user.password_hash)
         .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
 
     if !valid {
         return Err(StatusCode::UNAUTHORIZED);
     }
 
-    // Generate the JWT token
-    let token = create_token(&user.id).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    // Reject blocked accounts before issuing any tokens
+    if user.status == "blocked" {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    // Transparently migrate bcrypt accounts to Argon2id on successful login
+    if password::needs_rehash(&user.password_hash) {
+        if let Ok(new_hash) = password::hash(&payload.password) {
+            let _ = sqlx::query("UPDATE users SET password_hash = ? WHERE id = ?")
+                .bind(&new_hash)
+                .bind(&user.id)
+                .execute(pool.as_ref())
+                .await;
+        }
+    }
+
+    let (access_token, refresh_token, expires_in) = issue_tokens(&pool, &user.id, &user.role).await?;
 
-    Ok(Json(AuthResponse { token, user }))
-}
\ No newline at end of file
+    Ok(Json(AuthResponse { access_token, refresh_token, expires_in, user }))
+}
+
+/// Exchanges a valid refresh token for a new access/refresh token pair.
+///
+/// # Endpoint
+/// `POST /api/auth/refresh`
+///
+/// # Request Body
+/// - `refresh_token`: The refresh token previously issued to the client
+///
+/// # Returns
+/// - `200 OK` with `AuthResponse` containing a new token pair and user details
+/// - `401 Unauthorized` if the refresh token is missing, expired, or revoked
+/// - `500 Internal Server Error` if the operation fails
+///
+/// # Rotation
+/// The presented refresh token is revoked and a brand-new one is issued on
+/// every call, so a replayed (already-consumed) refresh token is rejected.
+#[utoipa::path(
+    post,
+    path = "/api/auth/refresh",
+    tag = "auth",
+    request_body = RefreshRequest,
+    responses(
+        (status = 200, description = "Token refreshed successfully", body = AuthResponse),
+        (status = 401, description = "Refresh token is invalid, expired, or revoked"),
+        (status = 500, description = "Failed to refresh token")
+    )
+)]
+pub async fn refresh(
+    State(pool): State<Arc<DbPool>>,
+    Json(payload): Json<RefreshRequest>,
+) -> Result<Json<AuthResponse>, StatusCode> {
+    let token_hash = hash_refresh_token(&payload.refresh_token);
+    let now = Utc::now().to_rfc3339();
+
+    let user_id = sqlx::query_scalar::<_, String>(
+        "SELECT user_id FROM refresh_tokens
+         WHERE token_hash = ? AND expires_at > ? AND revoked = 0"
+    )
+        .bind(&token_hash)
+        .bind(&now)
+        .fetch_one(pool.as_ref())
+        .await
+        .map_err(|_| StatusCode::UNAUTHORIZED)?;
+
+    // Rotate: revoke the presented token so it can't be replayed
+    sqlx::query("UPDATE refresh_tokens SET revoked = 1 WHERE token_hash = ?")
+        .bind(&token_hash)
+        .execute(pool.as_ref())
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let user = sqlx::query_as::<_, User>(
+        "SELECT id, email, name, password_hash, avatar, phone, role, status, created_at, updated_at
+         FROM users WHERE id = ?"
+    )
+        .bind(&user_id)
+        .fetch_one(pool.as_ref())
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let (access_token, refresh_token, expires_in) = issue_tokens(&pool, &user_id, &user.role).await?;
+
+    Ok(Json(AuthResponse { access_token, refresh_token, expires_in, user }))
+}
+
+/// Revokes a refresh token, logging the client out.
+///
+/// # Endpoint
+/// `POST /api/auth/logout`
+///
+/// # Request Body
+/// - `refresh_token`: The refresh token to revoke
+///
+/// # Returns
+/// - `204 No Content` on success, whether or not the token was found
+/// - `500 Internal Server Error` if the operation fails
+#[utoipa::path(
+    post,
+    path = "/api/auth/logout",
+    tag = "auth",
+    request_body = LogoutRequest,
+    responses(
+        (status = 204, description = "Logged out successfully"),
+        (status = 500, description = "Failed to log out")
+    )
+)]
+pub async fn logout(
+    State(pool): State<Arc<DbPool>>,
+    Json(payload): Json<LogoutRequest>,
+) -> Result<StatusCode, StatusCode> {
+    let token_hash = hash_refresh_token(&payload.refresh_token);
+
+    sqlx::query("UPDATE refresh_tokens SET revoked = 1 WHERE token_hash = ?")
+        .bind(&token_hash)
+        .execute(pool.as_ref())
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
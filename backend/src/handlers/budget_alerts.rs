@@ -0,0 +1,186 @@
+// Copyright (c) 2024 Tempo Budget
+// SPDX-License-Identifier: MIT
+//
+// Budget alert (spending threshold) management HTTP handlers.
+
+//! # Budget Alert Handlers
+//!
+//! This module provides HTTP handlers for managing budget spending
+//! thresholds:
+//! - `GET /api/budgets/:budget_id/alerts` - List a budget's alerts
+//! - `POST /api/budgets/:budget_id/alerts` - Create a new alert
+//! - `DELETE /api/budgets/:budget_id/alerts/:alert_id` - Delete an alert
+//! - `GET /api/budgets/:budget_id/alerts/status` - List currently-triggered alerts
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Json,
+};
+use std::sync::Arc;
+use uuid::Uuid;
+use chrono::Utc;
+
+use crate::{
+    auth::BudgetMembership,
+    error::AppError,
+    models::{BudgetAlert, CreateBudgetAlert},
+    DbPool,
+};
+
+/// Period assumed when a `CreateBudgetAlert` payload omits `period`.
+const DEFAULT_PERIOD: &str = "monthly";
+
+/// Retrieves all alerts configured for a budget.
+///
+/// # Endpoint
+/// `GET /api/budgets/:budget_id/alerts`
+///
+/// # Authentication
+/// Requires a valid JWT token. User must be a member of the budget.
+///
+/// # Returns
+/// - `200 OK` with array of `BudgetAlert` objects
+/// - `403 Forbidden` if the user is not a member of the budget
+pub async fn get_alerts(
+    State(pool): State<Arc<DbPool>>,
+    membership: BudgetMembership,
+    Path(budget_id): Path<String>,
+) -> Result<Json<Vec<BudgetAlert>>, AppError> {
+    membership.require_role("member").map_err(|_| AppError::Forbidden)?;
+
+    let alerts = sqlx::query_as::<_, BudgetAlert>(
+        "SELECT id, budget_id, category_id, threshold_type, threshold_value, limit_amount,
+         period, triggered, triggered_at, created_at
+         FROM budget_alerts WHERE budget_id = ?"
+    )
+        .bind(&budget_id)
+        .fetch_all(pool.as_ref())
+        .await?;
+
+    Ok(Json(alerts))
+}
+
+/// Creates a new spending threshold on a budget, optionally scoped to a
+/// single category.
+///
+/// # Endpoint
+/// `POST /api/budgets/:budget_id/alerts`
+///
+/// # Authentication
+/// Requires a valid JWT token. User must be a member of the budget.
+///
+/// # Request Body
+/// - `category_id`: (optional) Category to scope the alert to
+/// - `threshold_type`: "amount" or "percentage"
+/// - `threshold_value`: Absolute amount, or a percentage of `limit_amount`
+/// - `limit_amount`: (required for "percentage") The amount the percentage is of
+/// - `period`: (optional) Spending period, defaults to "monthly"
+///
+/// # Returns
+/// - `200 OK` with the created `BudgetAlert` object
+/// - `400 Bad Request` if `threshold_type` is "percentage" without `limit_amount`
+/// - `403 Forbidden` if the user is not a member of the budget
+pub async fn create_alert(
+    State(pool): State<Arc<DbPool>>,
+    membership: BudgetMembership,
+    Path(budget_id): Path<String>,
+    Json(payload): Json<CreateBudgetAlert>,
+) -> Result<Json<BudgetAlert>, AppError> {
+    membership.require_role("member").map_err(|_| AppError::Forbidden)?;
+
+    if payload.threshold_type == "percentage" && payload.limit_amount.is_none() {
+        return Err(AppError::BadRequest(
+            "limit_amount is required when threshold_type is \"percentage\"".to_string(),
+        ));
+    }
+
+    let id = Uuid::new_v4().to_string();
+    let now = Utc::now().to_rfc3339();
+    let period = payload.period.unwrap_or_else(|| DEFAULT_PERIOD.to_string());
+
+    sqlx::query(
+        "INSERT INTO budget_alerts (id, budget_id, category_id, threshold_type, threshold_value,
+         limit_amount, period, triggered, triggered_at, created_at)
+         VALUES (?, ?, ?, ?, ?, ?, ?, 0, NULL, ?)"
+    )
+        .bind(&id)
+        .bind(&budget_id)
+        .bind(&payload.category_id)
+        .bind(&payload.threshold_type)
+        .bind(payload.threshold_value)
+        .bind(payload.limit_amount)
+        .bind(&period)
+        .bind(&now)
+        .execute(pool.as_ref())
+        .await?;
+
+    let alert = sqlx::query_as::<_, BudgetAlert>(
+        "SELECT id, budget_id, category_id, threshold_type, threshold_value, limit_amount,
+         period, triggered, triggered_at, created_at
+         FROM budget_alerts WHERE id = ?"
+    )
+        .bind(&id)
+        .fetch_one(pool.as_ref())
+        .await?;
+
+    Ok(Json(alert))
+}
+
+/// Deletes a budget alert.
+///
+/// # Endpoint
+/// `DELETE /api/budgets/:budget_id/alerts/:alert_id`
+///
+/// # Authentication
+/// Requires a valid JWT token. User must be a member of the budget.
+///
+/// # Returns
+/// - `204 No Content` on successful deletion
+/// - `403 Forbidden` if the user is not a member of the budget
+pub async fn delete_alert(
+    State(pool): State<Arc<DbPool>>,
+    membership: BudgetMembership,
+    Path((budget_id, alert_id)): Path<(String, String)>,
+) -> Result<StatusCode, AppError> {
+    membership.require_role("member").map_err(|_| AppError::Forbidden)?;
+
+    sqlx::query("DELETE FROM budget_alerts WHERE id = ? AND budget_id = ?")
+        .bind(&alert_id)
+        .bind(&budget_id)
+        .execute(pool.as_ref())
+        .await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Lists alerts that have already crossed their threshold, so the frontend
+/// can surface something like "you've spent 80% of groceries this month".
+///
+/// # Endpoint
+/// `GET /api/budgets/:budget_id/alerts/status`
+///
+/// # Authentication
+/// Requires a valid JWT token. User must be a member of the budget.
+///
+/// # Returns
+/// - `200 OK` with array of triggered `BudgetAlert` objects
+/// - `403 Forbidden` if the user is not a member of the budget
+pub async fn get_alert_status(
+    State(pool): State<Arc<DbPool>>,
+    membership: BudgetMembership,
+    Path(budget_id): Path<String>,
+) -> Result<Json<Vec<BudgetAlert>>, AppError> {
+    membership.require_role("member").map_err(|_| AppError::Forbidden)?;
+
+    let alerts = sqlx::query_as::<_, BudgetAlert>(
+        "SELECT id, budget_id, category_id, threshold_type, threshold_value, limit_amount,
+         period, triggered, triggered_at, created_at
+         FROM budget_alerts WHERE budget_id = ? AND triggered = 1"
+    )
+        .bind(&budget_id)
+        .fetch_all(pool.as_ref())
+        .await?;
+
+    Ok(Json(alerts))
+}
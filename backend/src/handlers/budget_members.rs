@@ -20,7 +20,10 @@ use uuid::Uuid;
 use chrono::Utc;
 
 use crate::{
-    auth::AuthUser,
+    auth::BudgetMembership,
+    error::AppError,
+    events::{BudgetEvent, EventRegistry},
+    mail,
     models::{BudgetMemberWithUser, InviteMemberRequest},
     DbPool,
 };
@@ -42,25 +45,10 @@ use crate::{
 /// - `500 Internal Server Error` if the query fails
 pub async fn get_budget_members(
     State(pool): State<Arc<DbPool>>,
-    auth: AuthUser,
+    membership: BudgetMembership,
     Path(budget_id): Path<String>,
 ) -> Result<Json<Vec<BudgetMemberWithUser>>, StatusCode> {
-    // Verify that the user has access to the budget
-    let is_member = sqlx::query_scalar::<_, i64>(
-        "SELECT COUNT(*) FROM budget_members WHERE budget_id = ? AND user_id = ?"
-    )
-        .bind(&budget_id)
-        .bind(&auth.user_id)
-        .fetch_one(pool.as_ref())
-        .await
-        .map_err(|e| {
-            tracing::error!("Failed to check membership: {:?}", e);
-            StatusCode::INTERNAL_SERVER_ERROR
-        })?;
-
-    if is_member == 0 {
-        return Err(StatusCode::FORBIDDEN);
-    }
+    membership.require_role("member")?;
 
     // Retrieve members with user information
     let members = sqlx::query_as::<_, BudgetMemberWithUser>(
@@ -101,27 +89,15 @@ pub async fn get_budget_members(
 /// - `201 Created` on successful invitation
 /// - `403 Forbidden` if the user is not the budget owner
 /// - `404 Not Found` if the invited email doesn't exist
-/// - `409 Conflict` if the user is already a member
+/// - `409 Conflict` if the user is already a member or already has a pending invitation
 /// - `500 Internal Server Error` if the invitation fails
 pub async fn invite_member(
     State(pool): State<Arc<DbPool>>,
-    auth: AuthUser,
+    membership: BudgetMembership,
     Path(budget_id): Path<String>,
     Json(payload): Json<InviteMemberRequest>,
-) -> Result<StatusCode, StatusCode> {
-    // Verify that the user is the owner
-    let is_owner = sqlx::query_scalar::<_, i64>(
-        "SELECT COUNT(*) FROM budget_members WHERE budget_id = ? AND user_id = ? AND role = 'owner'"
-    )
-        .bind(&budget_id)
-        .bind(&auth.user_id)
-        .fetch_one(pool.as_ref())
-        .await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-
-    if is_owner == 0 {
-        return Err(StatusCode::FORBIDDEN);
-    }
+) -> Result<StatusCode, AppError> {
+    membership.require_role("owner").map_err(|_| AppError::Forbidden)?;
 
     // Verify that the email exists in the system
     let user_exists = sqlx::query_scalar::<_, i64>(
@@ -129,11 +105,10 @@ pub async fn invite_member(
     )
         .bind(&payload.email)
         .fetch_one(pool.as_ref())
-        .await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        .await?;
 
     if user_exists == 0 {
-        return Err(StatusCode::NOT_FOUND);
+        return Err(AppError::NotFound);
     }
 
     // Check if the user is already a member
@@ -145,14 +120,16 @@ pub async fn invite_member(
         .bind(&budget_id)
         .bind(&payload.email)
         .fetch_one(pool.as_ref())
-        .await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        .await?;
 
     if already_member > 0 {
-        return Err(StatusCode::CONFLICT);
+        return Err(AppError::Conflict("user is already a member of this budget".to_string()));
     }
 
-    // Create the invitation
+    // Create the invitation. The `idx_budget_invitations_pending_unique`
+    // index is the authoritative guard against a duplicate pending
+    // invitation slipping in through a race between two concurrent
+    // requests; `AppError`'s `From<sqlx::Error>` turns that into a 409.
     let id = Uuid::new_v4().to_string();
     let now = Utc::now().to_rfc3339();
 
@@ -162,16 +139,29 @@ pub async fn invite_member(
     )
         .bind(&id)
         .bind(&budget_id)
-        .bind(&auth.user_id)
+        .bind(&membership.user_id)
         .bind(&payload.email)
         .bind(&payload.role)
         .bind(&now)
         .execute(pool.as_ref())
+        .await?;
+
+    // Notify the invitee by email; a delivery failure shouldn't fail the request
+    let budget_name = sqlx::query_scalar::<_, String>("SELECT name FROM budgets WHERE id = ?")
+        .bind(&budget_id)
+        .fetch_one(pool.as_ref())
         .await
-        .map_err(|e| {
-            tracing::error!("Failed to create invitation: {:?}", e);
-            StatusCode::INTERNAL_SERVER_ERROR
-        })?;
+        .unwrap_or_else(|_| "a budget".to_string());
+
+    mail::enqueue(
+        &payload.email,
+        &format!("You've been invited to join {budget_name}"),
+        &format!(
+            "You've been invited to join \"{budget_name}\" as {}. Sign in to Tempo Budget and check \
+             your pending invitations to accept or reject.",
+            payload.role
+        ),
+    );
 
     Ok(StatusCode::CREATED)
 }
@@ -194,23 +184,22 @@ pub async fn invite_member(
 /// - `500 Internal Server Error` if the removal fails
 pub async fn remove_member(
     State(pool): State<Arc<DbPool>>,
-    auth: AuthUser,
+    State(events): State<EventRegistry>,
+    membership: BudgetMembership,
     Path((budget_id, member_id)): Path<(String, String)>,
 ) -> Result<StatusCode, StatusCode> {
-    // Verify that the user is the owner
-    let is_owner = sqlx::query_scalar::<_, i64>(
-        "SELECT COUNT(*) FROM budget_members WHERE budget_id = ? AND user_id = ? AND role = 'owner'"
+    membership.require_role("owner")?;
+
+    // Look up the member's user id so we can announce who left
+    let removed_user_id = sqlx::query_scalar::<_, String>(
+        "SELECT user_id FROM budget_members WHERE id = ? AND budget_id = ?"
     )
+        .bind(&member_id)
         .bind(&budget_id)
-        .bind(&auth.user_id)
-        .fetch_one(pool.as_ref())
+        .fetch_optional(pool.as_ref())
         .await
         .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
 
-    if is_owner == 0 {
-        return Err(StatusCode::FORBIDDEN);
-    }
-
     // Remove the member
     sqlx::query("DELETE FROM budget_members WHERE id = ? AND budget_id = ?")
         .bind(&member_id)
@@ -219,5 +208,9 @@ pub async fn remove_member(
         .await
         .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
 
+    if let Some(user_id) = removed_user_id {
+        events.publish(&budget_id, BudgetEvent::MemberRemoved { user_id });
+    }
+
     Ok(StatusCode::NO_CONTENT)
 }
\ No newline at end of file
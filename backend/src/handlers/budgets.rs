@@ -12,7 +12,7 @@
 //! - `DELETE /api/budgets/:id` - Delete a budget
 
 use axum::{
-    extract::{Path, State},
+    extract::{Path, Query, State},
     http::StatusCode,
     Json,
 };
@@ -22,13 +22,20 @@ use chrono::Utc;
 use utoipa;
 
 use crate::{
-    auth::AuthUser,
-    models::{Budget, CreateBudget},
+    auth::{create_share_token, verify_share_token, AuthUser, SHARE_TOKEN_TTL_DAYS},
+    error::AppError,
+    models::{
+        Budget, BudgetListQuery, BudgetListResponse, BudgetSnapshot, Category, CreateBudget,
+        ShareLinkResponse, Transaction,
+    },
     DbPool,
 };
 use crate::models::InviteMemberRequest;
 
-/// Retrieves all budgets for the authenticated user.
+/// Default page size for `GET /api/budgets` when `limit` isn't given.
+const DEFAULT_BUDGET_LIMIT: i64 = 20;
+
+/// Retrieves a page of budgets for the authenticated user.
 ///
 /// # Endpoint
 /// `GET /api/budgets`
@@ -36,15 +43,30 @@ use crate::models::InviteMemberRequest;
 /// # Authentication
 /// Requires a valid JWT token in the Authorization header.
 ///
+/// # Query Parameters
+/// - `limit`: Maximum number of budgets to return (default 20)
+/// - `offset`: Number of budgets to skip (default 0)
+/// - `budget_type`: Filter by "personal" or "group"
+/// - `is_active`: Filter by active status (0 or 1)
+///
 /// # Returns
-/// - `200 OK` with array of `Budget` objects
+/// - `200 OK` with a `BudgetListResponse` envelope
 /// - `500 Internal Server Error` if the query fails
+///
+/// # Notes
+/// Soft-deleted budgets (see `delete_budget`) are never included.
 #[utoipa::path(
     get,
     path = "/api/budgets",
     tag = "budgets",
+    params(
+        ("limit" = Option<i64>, Query, description = "Maximum number of budgets to return (default 20)"),
+        ("offset" = Option<i64>, Query, description = "Number of budgets to skip (default 0)"),
+        ("budget_type" = Option<String>, Query, description = "Filter by budget type"),
+        ("is_active" = Option<i32>, Query, description = "Filter by active status"),
+    ),
     responses(
-        (status = 200, description = "List of user's budgets", body = Vec<Budget>),
+        (status = 200, description = "Paginated list of the user's budgets", body = BudgetListResponse),
         (status = 500, description = "Failed to fetch budgets")
     ),
     security(("bearer_auth" = []))
@@ -52,12 +74,45 @@ use crate::models::InviteMemberRequest;
 pub async fn get_budgets(
     State(pool): State<Arc<DbPool>>,
     auth: AuthUser,
-) -> Result<Json<Vec<Budget>>, StatusCode> {
-    let budgets = sqlx::query_as::<_, Budget>(
+    Query(query): Query<BudgetListQuery>,
+) -> Result<Json<BudgetListResponse>, StatusCode> {
+    let limit = query.limit.unwrap_or(DEFAULT_BUDGET_LIMIT);
+    let offset = query.offset.unwrap_or(0);
+
+    let total = sqlx::query_scalar::<_, i64>(
+        "SELECT COUNT(*) FROM budgets
+         WHERE user_id = ? AND deleted_at IS NULL
+           AND (? IS NULL OR budget_type = ?)
+           AND (? IS NULL OR is_active = ?)"
+    )
+        .bind(&auth.user_id)
+        .bind(&query.budget_type)
+        .bind(&query.budget_type)
+        .bind(query.is_active)
+        .bind(query.is_active)
+        .fetch_one(pool.as_ref())
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to count budgets: {:?}", e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    let items = sqlx::query_as::<_, Budget>(
         "SELECT id, user_id, name, budget_type, is_active, created_at, updated_at
-         FROM budgets WHERE user_id = ?"
+         FROM budgets
+         WHERE user_id = ? AND deleted_at IS NULL
+           AND (? IS NULL OR budget_type = ?)
+           AND (? IS NULL OR is_active = ?)
+         ORDER BY created_at DESC
+         LIMIT ? OFFSET ?"
     )
         .bind(&auth.user_id)
+        .bind(&query.budget_type)
+        .bind(&query.budget_type)
+        .bind(query.is_active)
+        .bind(query.is_active)
+        .bind(limit)
+        .bind(offset)
         .fetch_all(pool.as_ref())
         .await
         .map_err(|e| {
@@ -65,7 +120,7 @@ pub async fn get_budgets(
             StatusCode::INTERNAL_SERVER_ERROR
         })?;
 
-    Ok(Json(budgets))
+    Ok(Json(BudgetListResponse { items, total, limit, offset }))
 }
 
 /// Creates a new budget.
@@ -85,7 +140,9 @@ pub async fn get_budgets(
 /// - `500 Internal Server Error` if creation fails
 ///
 /// # Notes
-/// For group budgets, the creator is automatically added as the owner.
+/// For group budgets, the creator is automatically added as the owner. Both
+/// inserts run inside a single transaction so a failure adding the owner
+/// membership can't leave a group budget without one.
 #[utoipa::path(
     post,
     path = "/api/budgets",
@@ -101,11 +158,13 @@ pub async fn create_budget(
     State(pool): State<Arc<DbPool>>,
     auth: AuthUser,
     Json(payload): Json<CreateBudget>,
-) -> Result<Json<Budget>, StatusCode> {
+) -> Result<Json<Budget>, AppError> {
     let id = Uuid::new_v4().to_string();
     let user_id = auth.user_id.clone();
     let now = Utc::now().to_rfc3339();
 
+    let mut tx = pool.begin().await?;
+
     sqlx::query(
         "INSERT INTO budgets (id, user_id, name, budget_type, is_active, created_at, updated_at)
          VALUES (?, ?, ?, ?, 0, ?, ?)"
@@ -116,12 +175,8 @@ pub async fn create_budget(
         .bind(&payload.budget_type)
         .bind(&now)
         .bind(&now)
-        .execute(pool.as_ref())
-        .await
-        .map_err(|e| {
-            tracing::error!("Failed to insert budget: {:?}", e);
-            StatusCode::INTERNAL_SERVER_ERROR
-        })?;
+        .execute(&mut *tx)
+        .await?;
 
     // For group budgets, add the creator as owner
     if payload.budget_type == "group" {
@@ -133,12 +188,8 @@ pub async fn create_budget(
             .bind(&id)
             .bind(&user_id)
             .bind(&now)
-            .execute(pool.as_ref())
-            .await
-            .map_err(|e| {
-                tracing::error!("Failed to insert budget member: {:?}", e);
-                StatusCode::INTERNAL_SERVER_ERROR
-            })?;
+            .execute(&mut *tx)
+            .await?;
     }
 
     let budget = sqlx::query_as::<_, Budget>(
@@ -146,12 +197,10 @@ pub async fn create_budget(
          FROM budgets WHERE id = ?"
     )
         .bind(&id)
-        .fetch_one(pool.as_ref())
-        .await
-        .map_err(|e| {
-            tracing::error!("Failed to fetch created budget: {:?}", e);
-            StatusCode::INTERNAL_SERVER_ERROR
-        })?;
+        .fetch_one(&mut *tx)
+        .await?;
+
+    tx.commit().await?;
 
     Ok(Json(budget))
 }
@@ -185,7 +234,7 @@ pub async fn get_budget(
 ) -> Result<Json<Budget>, StatusCode> {
     let budget = sqlx::query_as::<_, Budget>(
         "SELECT id, user_id, name, budget_type, is_active, created_at, updated_at
-         FROM budgets WHERE id = ?"
+         FROM budgets WHERE id = ? AND deleted_at IS NULL"
     )
         .bind(&id)
         .fetch_one(pool.as_ref())
@@ -217,6 +266,11 @@ pub async fn get_budget(
 /// # Authorization
 /// User must be either the budget owner (for group budgets) or the creator
 /// (for personal budgets).
+///
+/// # Notes
+/// This is a soft delete: it sets `deleted_at` rather than removing the
+/// row, so history survives. Every read in this module filters on
+/// `deleted_at IS NULL`, so a deleted budget behaves as if it were gone.
 #[utoipa::path(
     delete,
     path = "/api/budgets/{id}",
@@ -248,7 +302,7 @@ pub async fn delete_budget(
 
     // Check if it's the user's own budget (for personal budgets)
     let is_my_budget = sqlx::query_scalar::<_, i64>(
-        "SELECT COUNT(*) FROM budgets WHERE id = ? AND user_id = ?"
+        "SELECT COUNT(*) FROM budgets WHERE id = ? AND user_id = ? AND deleted_at IS NULL"
     )
         .bind(&id)
         .bind(&auth.user_id)
@@ -260,8 +314,9 @@ pub async fn delete_budget(
         return Err(StatusCode::FORBIDDEN);
     }
 
-    // Delete the budget (cascade deletes related records)
-    sqlx::query("DELETE FROM budgets WHERE id = ?")
+    // Soft-delete the budget
+    sqlx::query("UPDATE budgets SET deleted_at = ? WHERE id = ?")
+        .bind(Utc::now().to_rfc3339())
         .bind(&id)
         .execute(pool.as_ref())
         .await
@@ -271,4 +326,144 @@ pub async fn delete_budget(
         })?;
 
     Ok(StatusCode::NO_CONTENT)
+}
+
+/// Mints a read-only share link for a budget.
+///
+/// # Endpoint
+/// `POST /api/budgets/:id/share`
+///
+/// # Authentication
+/// Requires a valid JWT token in the Authorization header.
+///
+/// # Path Parameters
+/// - `id`: The budget's unique identifier
+///
+/// # Returns
+/// - `200 OK` with a `ShareLinkResponse` containing the link and its expiry
+/// - `403 Forbidden` if the user doesn't own the budget
+/// - `500 Internal Server Error` if token creation fails
+///
+/// # Authorization
+/// Same rule as deletion: the user must be either the budget owner (group
+/// budgets) or the creator (personal budgets).
+#[utoipa::path(
+    post,
+    path = "/api/budgets/{id}/share",
+    tag = "budgets",
+    params(
+        ("id" = String, Path, description = "Budget unique identifier")
+    ),
+    responses(
+        (status = 200, description = "Share link created", body = ShareLinkResponse),
+        (status = 403, description = "Not authorized to share this budget"),
+        (status = 500, description = "Failed to create share link")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn share_budget(
+    State(pool): State<Arc<DbPool>>,
+    auth: AuthUser,
+    Path(id): Path<String>,
+) -> Result<Json<ShareLinkResponse>, StatusCode> {
+    let is_owner = sqlx::query_scalar::<_, i64>(
+        "SELECT COUNT(*) FROM budget_members WHERE budget_id = ? AND user_id = ? AND role = 'owner'"
+    )
+        .bind(&id)
+        .bind(&auth.user_id)
+        .fetch_one(pool.as_ref())
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let is_my_budget = sqlx::query_scalar::<_, i64>(
+        "SELECT COUNT(*) FROM budgets WHERE id = ? AND user_id = ? AND deleted_at IS NULL"
+    )
+        .bind(&id)
+        .bind(&auth.user_id)
+        .fetch_one(pool.as_ref())
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    if is_owner == 0 && is_my_budget == 0 {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    let token = create_share_token(&id).map_err(|e| {
+        tracing::error!("Failed to create share token: {:?}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    let expires_at = (Utc::now() + chrono::Duration::days(SHARE_TOKEN_TTL_DAYS)).to_rfc3339();
+
+    Ok(Json(ShareLinkResponse {
+        url: format!("/api/shared/{}", token),
+        expires_at,
+    }))
+}
+
+/// Retrieves a read-only snapshot of a shared budget.
+///
+/// # Endpoint
+/// `GET /api/shared/:token`
+///
+/// # Authentication
+/// None. Access is granted solely by possession of a valid, unexpired share
+/// token minted by `POST /api/budgets/:id/share`.
+///
+/// # Path Parameters
+/// - `token`: The share token
+///
+/// # Returns
+/// - `200 OK` with a `BudgetSnapshot`
+/// - `401 Unauthorized` if the token is invalid, expired, or not a share token
+/// - `404 Not Found` if the budget no longer exists
+/// - `500 Internal Server Error` if the query fails
+#[utoipa::path(
+    get,
+    path = "/api/shared/{token}",
+    tag = "budgets",
+    params(
+        ("token" = String, Path, description = "Share token")
+    ),
+    responses(
+        (status = 200, description = "Read-only budget snapshot", body = BudgetSnapshot),
+        (status = 401, description = "Share token is invalid, expired, or out of scope"),
+        (status = 404, description = "Budget not found"),
+        (status = 500, description = "Failed to fetch budget snapshot")
+    )
+)]
+pub async fn get_shared_budget(
+    State(pool): State<Arc<DbPool>>,
+    Path(token): Path<String>,
+) -> Result<Json<BudgetSnapshot>, StatusCode> {
+    let claims = verify_share_token(&token).map_err(|_| StatusCode::UNAUTHORIZED)?;
+
+    let budget = sqlx::query_as::<_, Budget>(
+        "SELECT id, user_id, name, budget_type, is_active, created_at, updated_at
+         FROM budgets WHERE id = ? AND deleted_at IS NULL"
+    )
+        .bind(&claims.budget_id)
+        .fetch_one(pool.as_ref())
+        .await
+        .map_err(|_| StatusCode::NOT_FOUND)?;
+
+    let categories = sqlx::query_as::<_, Category>(
+        "SELECT id, budget_id, name, amount, created_at FROM categories WHERE budget_id = ?"
+    )
+        .bind(&claims.budget_id)
+        .fetch_all(pool.as_ref())
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let recent_transactions = sqlx::query_as::<_, Transaction>(
+        "SELECT id, budget_id, category_id, title, amount, transaction_type, date, comment,
+         is_recurring, created_at, import_id
+         FROM transactions WHERE budget_id = ? ORDER BY date DESC LIMIT 20"
+    )
+        .bind(&claims.budget_id)
+        .fetch_all(pool.as_ref())
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(Json(BudgetSnapshot { budget, categories, recent_transactions }))
 }
\ No newline at end of file
@@ -0,0 +1,57 @@
+// Copyright (c) 2024 Tempo Budget
+// SPDX-License-Identifier: MIT
+//
+// Real-time group-budget event stream.
+
+//! # Event Handlers
+//!
+//! This module provides the live-update counterpart to `GET
+//! /api/budgets/:budget_id/members`:
+//! - `GET /api/budgets/:budget_id/events` - Subscribe to a budget's events (SSE)
+
+use axum::{
+    extract::{Path, State},
+    response::sse::{Event, KeepAlive, Sse},
+};
+use futures::stream::Stream;
+use std::convert::Infallible;
+use tokio_stream::wrappers::BroadcastStream;
+use tokio_stream::StreamExt as _;
+
+use crate::{auth::BudgetMembership, events::EventRegistry};
+
+/// Subscribes to a budget's live event stream over Server-Sent Events.
+///
+/// # Endpoint
+/// `GET /api/budgets/:budget_id/events`
+///
+/// # Authentication
+/// Requires a valid JWT token. User must be a member of the budget; this is
+/// checked once, on connect, via the same `BudgetMembership` guard used by
+/// `GET /api/budgets/:budget_id/members`.
+///
+/// # Path Parameters
+/// - `budget_id`: The budget's unique identifier
+///
+/// # Returns
+/// An SSE stream of JSON-encoded `BudgetEvent`s (`MemberJoined`,
+/// `MemberRemoved`, `BudgetUpdated`, `AlertTriggered`). The connection is kept alive with
+/// periodic comment pings so idle proxies don't close it.
+pub async fn budget_events(
+    State(events): State<EventRegistry>,
+    // Presence of `membership` already proves the caller is a member of the
+    // budget named by the `:budget_id` path param; nothing further to check.
+    _membership: BudgetMembership,
+    Path(budget_id): Path<String>,
+) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    let receiver = events.subscribe(&budget_id);
+
+    let stream = BroadcastStream::new(receiver).filter_map(|result| {
+        result.ok().map(|event| {
+            let data = serde_json::to_string(&event).unwrap_or_default();
+            Ok(Event::default().data(data))
+        })
+    });
+
+    Sse::new(stream).keep_alive(KeepAlive::default())
+}
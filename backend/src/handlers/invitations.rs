@@ -21,6 +21,8 @@ use chrono::Utc;
 
 use crate::{
     auth::AuthUser,
+    error::AppError,
+    events::{BudgetEvent, EventRegistry},
     models::BudgetInvitationWithDetails,
     DbPool,
 };
@@ -99,16 +101,21 @@ pub async fn get_my_invitations(
 /// - `400 Bad Request` if the invitation is not pending
 /// - `403 Forbidden` if the user is not the intended recipient
 /// - `404 Not Found` if the invitation doesn't exist
+/// - `409 Conflict` if the user is already a member of the budget
 /// - `500 Internal Server Error` if the operation fails
 ///
 /// # Side Effects
 /// - Creates a new budget member record
 /// - Updates the invitation status to "accepted"
+///
+/// Both writes run inside a single transaction so an invitation can't end
+/// up marked accepted without a corresponding membership row, or vice versa.
 pub async fn accept_invitation(
     State(pool): State<Arc<DbPool>>,
+    State(events): State<EventRegistry>,
     auth: AuthUser,
     Path(invitation_id): Path<String>,
-) -> Result<StatusCode, StatusCode> {
+) -> Result<StatusCode, AppError> {
     // Get the invitation details
     let invitation = sqlx::query_as::<_, (String, String, String, String)>(
         "SELECT budget_id, invitee_email, role, status FROM budget_invitations WHERE id = ?"
@@ -116,12 +123,12 @@ pub async fn accept_invitation(
         .bind(&invitation_id)
         .fetch_one(pool.as_ref())
         .await
-        .map_err(|_| StatusCode::NOT_FOUND)?;
+        .map_err(|_| AppError::NotFound)?;
 
     let (budget_id, invitee_email, role, status) = invitation;
 
     if status != "pending" {
-        return Err(StatusCode::BAD_REQUEST);
+        return Err(AppError::BadRequest("invitation is no longer pending".to_string()));
     }
 
     // Verify that the invitation is for this user
@@ -130,17 +137,20 @@ pub async fn accept_invitation(
     )
         .bind(&auth.user_id)
         .fetch_one(pool.as_ref())
-        .await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        .await?;
 
     if user_email != invitee_email {
-        return Err(StatusCode::FORBIDDEN);
+        return Err(AppError::Forbidden);
     }
 
-    // Add the member to the budget
+    // Add the member to the budget and mark the invitation accepted
+    // together; the unique index on `budget_members` turns a duplicate
+    // membership race into a 409 rather than a 500.
     let member_id = Uuid::new_v4().to_string();
     let now = Utc::now().to_rfc3339();
 
+    let mut tx = pool.begin().await?;
+
     sqlx::query(
         "INSERT INTO budget_members (id, budget_id, user_id, role, created_at) VALUES (?, ?, ?, ?, ?)"
     )
@@ -149,19 +159,17 @@ pub async fn accept_invitation(
         .bind(&auth.user_id)
         .bind(&role)
         .bind(&now)
-        .execute(pool.as_ref())
-        .await
-        .map_err(|e| {
-            tracing::error!("Failed to add member: {:?}", e);
-            StatusCode::INTERNAL_SERVER_ERROR
-        })?;
+        .execute(&mut *tx)
+        .await?;
 
-    // Mark the invitation as accepted
     sqlx::query("UPDATE budget_invitations SET status = 'accepted' WHERE id = ?")
         .bind(&invitation_id)
-        .execute(pool.as_ref())
-        .await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        .execute(&mut *tx)
+        .await?;
+
+    tx.commit().await?;
+
+    events.publish(&budget_id, BudgetEvent::MemberJoined { user_id: auth.user_id.clone(), role });
 
     Ok(StatusCode::OK)
 }
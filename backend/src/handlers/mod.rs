@@ -13,8 +13,12 @@ pub mod auth;
 pub mod budgets;
 pub mod transactions;
 pub mod categories;
+pub(crate) mod attachments;
+pub(crate) mod budget_alerts;
 pub(crate) mod budget_members;
+pub(crate) mod events;
 pub(crate) mod invitations;
+pub(crate) mod reports;
 
 pub use auth::*;
 pub use budgets::*;
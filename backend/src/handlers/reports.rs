@@ -0,0 +1,119 @@
+// Copyright (c) 2024 Tempo Budget
+// SPDX-License-Identifier: MIT
+//
+// Budget spending report HTTP handlers.
+
+//! # Report Handlers
+//!
+//! This module provides HTTP handlers for the scheduled spending-summary
+//! reports:
+//! - `PUT /api/budgets/:budget_id/report-settings` - Enable/disable and pick cadence
+//! - `POST /api/budgets/:budget_id/report/preview` - Render the current period's report
+
+use axum::{
+    extract::{Path, State},
+    Json,
+};
+use std::sync::Arc;
+use chrono::Utc;
+
+use crate::{
+    auth::BudgetMembership,
+    error::AppError,
+    models::{BudgetReportSettings, ReportPreview, UpdateReportSettings},
+    reports,
+    DbPool,
+};
+
+/// Updates a budget's report schedule, enabling or disabling scheduled
+/// emails and/or changing their cadence.
+///
+/// # Endpoint
+/// `PUT /api/budgets/:budget_id/report-settings`
+///
+/// # Authentication
+/// Requires a valid JWT token. User must be a member of the budget.
+///
+/// # Request Body
+/// - `enabled`: Whether scheduled report emails should be sent
+/// - `cadence`: "weekly" or "monthly"
+///
+/// # Returns
+/// - `200 OK` with the updated `BudgetReportSettings`
+/// - `400 Bad Request` if `cadence` isn't "weekly" or "monthly"
+/// - `403 Forbidden` if the user is not a member of the budget
+pub async fn update_report_settings(
+    State(pool): State<Arc<DbPool>>,
+    membership: BudgetMembership,
+    Path(budget_id): Path<String>,
+    Json(payload): Json<UpdateReportSettings>,
+) -> Result<Json<BudgetReportSettings>, AppError> {
+    membership.require_role("member").map_err(|_| AppError::Forbidden)?;
+
+    if payload.cadence != "weekly" && payload.cadence != "monthly" {
+        return Err(AppError::BadRequest("cadence must be \"weekly\" or \"monthly\"".to_string()));
+    }
+
+    let now = Utc::now();
+    let next_send_at = payload.enabled.then(|| reports::next_send_at(&payload.cadence, now).to_rfc3339());
+
+    sqlx::query(
+        "INSERT INTO budget_report_settings (budget_id, enabled, cadence, next_send_at, updated_at)
+         VALUES (?, ?, ?, ?, ?)
+         ON CONFLICT(budget_id) DO UPDATE SET
+            enabled = excluded.enabled,
+            cadence = excluded.cadence,
+            next_send_at = excluded.next_send_at,
+            updated_at = excluded.updated_at"
+    )
+        .bind(&budget_id)
+        .bind(payload.enabled as i32)
+        .bind(&payload.cadence)
+        .bind(&next_send_at)
+        .bind(now.to_rfc3339())
+        .execute(pool.as_ref())
+        .await?;
+
+    let settings = sqlx::query_as::<_, BudgetReportSettings>(
+        "SELECT budget_id, enabled, cadence, next_send_at, updated_at
+         FROM budget_report_settings WHERE budget_id = ?"
+    )
+        .bind(&budget_id)
+        .fetch_one(pool.as_ref())
+        .await?;
+
+    Ok(Json(settings))
+}
+
+/// Renders the current period's spending report synchronously, without
+/// sending or scheduling anything. Lets the frontend show a member what a
+/// report would look like before they enable scheduled emails.
+///
+/// # Endpoint
+/// `POST /api/budgets/:budget_id/report/preview`
+///
+/// # Authentication
+/// Requires a valid JWT token. User must be a member of the budget.
+///
+/// # Returns
+/// - `200 OK` with a `ReportPreview`
+/// - `403 Forbidden` if the user is not a member of the budget
+pub async fn preview_report(
+    State(pool): State<Arc<DbPool>>,
+    membership: BudgetMembership,
+    Path(budget_id): Path<String>,
+) -> Result<Json<ReportPreview>, AppError> {
+    membership.require_role("member").map_err(|_| AppError::Forbidden)?;
+
+    let cadence = sqlx::query_scalar::<_, String>(
+        "SELECT cadence FROM budget_report_settings WHERE budget_id = ?"
+    )
+        .bind(&budget_id)
+        .fetch_optional(pool.as_ref())
+        .await?
+        .unwrap_or_else(|| "monthly".to_string());
+
+    let preview = reports::build(&pool, &budget_id, &cadence, Utc::now().date_naive()).await?;
+
+    Ok(Json(preview))
+}
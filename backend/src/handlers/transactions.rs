@@ -8,6 +8,7 @@
 //! This module provides HTTP handlers for transaction management:
 //! - `GET /api/budgets/:budget_id/transactions` - List transactions
 //! - `POST /api/budgets/:budget_id/transactions` - Create a transaction
+//! - `POST /api/budgets/:budget_id/transactions/bulk` - Import a batch of transactions
 //! - `DELETE /api/transactions/:id` - Delete a transaction
 //!
 //! Also includes recurring transaction handlers:
@@ -15,9 +16,11 @@
 //! - `POST /api/budgets/:budget_id/recurring` - Create a recurring transaction
 //! - `PUT /api/recurring/:id/toggle` - Toggle recurring transaction active status
 //! - `DELETE /api/recurring/:id` - Delete a recurring transaction
+//! - `POST /api/recurring/:id/run` - Force-generate a single template's due transactions
+//! - `GET /api/budgets/:budget_id/recurring/upcoming` - Preview future occurrences
 
 use axum::{
-    extract::{Path, State},
+    extract::{Path, Query, State},
     http::StatusCode,
     Json,
 };
@@ -27,13 +30,26 @@ use chrono::Utc;
 use utoipa;
 
 use crate::{
+    auth::{AuthUser, BudgetMembership},
+    events::EventRegistry,
     models::{
-        CreateRecurringTransaction, CreateTransaction, RecurringTransaction, Transaction,
+        BulkImportError, BulkImportResponse, CreateRecurringTransaction, CreateTransaction,
+        RecurringTransaction, RunRecurringResponse, Transaction, TransactionListQuery,
+        TransactionListResponse, UpcomingOccurrence, UpcomingQuery,
     },
+    recurring,
     DbPool,
 };
 
-/// Retrieves all transactions for a specific budget.
+/// Default lookahead window, in days, for `GET .../recurring/upcoming` when
+/// the `days` query parameter is omitted.
+const DEFAULT_UPCOMING_DAYS: i64 = 30;
+
+/// Default page size for `GET .../transactions` when `limit` isn't given.
+const DEFAULT_TRANSACTION_LIMIT: i64 = 50;
+
+/// Retrieves a filtered, paginated page of transactions for a specific
+/// budget.
 ///
 /// # Endpoint
 /// `GET /api/budgets/:budget_id/transactions`
@@ -41,35 +57,123 @@ use crate::{
 /// # Path Parameters
 /// - `budget_id`: The budget's unique identifier
 ///
+/// # Query Parameters
+/// - `since`/`until`: Only include transactions within this date range
+/// - `category_id`: Filter by category
+/// - `transaction_type`: Filter by "income" or "expense"
+/// - `min_amount`/`max_amount`: Only include transactions within this amount range
+/// - `search`: Free-text search over `title` and `comment`
+/// - `limit`: Maximum number of transactions to return (default 50)
+/// - `offset`: Number of transactions to skip (default 0)
+///
 /// # Returns
-/// - `200 OK` with array of `Transaction` objects (sorted by date descending)
+/// - `200 OK` with a `TransactionListResponse` envelope (rows sorted by date descending)
 /// - `500 Internal Server Error` if the query fails
 #[utoipa::path(
     get,
     path = "/api/budgets/{budget_id}/transactions",
     tag = "transactions",
     params(
-        ("budget_id" = String, Path, description = "Budget unique identifier")
+        ("budget_id" = String, Path, description = "Budget unique identifier"),
+        ("since" = Option<String>, Query, description = "Only include transactions on or after this date"),
+        ("until" = Option<String>, Query, description = "Only include transactions on or before this date"),
+        ("category_id" = Option<String>, Query, description = "Filter by category"),
+        ("transaction_type" = Option<String>, Query, description = "Filter by \"income\" or \"expense\""),
+        ("min_amount" = Option<f64>, Query, description = "Minimum transaction amount"),
+        ("max_amount" = Option<f64>, Query, description = "Maximum transaction amount"),
+        ("search" = Option<String>, Query, description = "Free-text search over title and comment"),
+        ("limit" = Option<i64>, Query, description = "Maximum number of transactions to return (default 50)"),
+        ("offset" = Option<i64>, Query, description = "Number of transactions to skip (default 0)"),
     ),
     responses(
-        (status = 200, description = "List of transactions", body = Vec<Transaction>),
+        (status = 200, description = "Paginated list of transactions", body = TransactionListResponse),
         (status = 500, description = "Failed to fetch transactions")
     )
 )]
 pub async fn get_transactions(
     State(pool): State<Arc<DbPool>>,
     Path(budget_id): Path<String>,
-) -> Result<Json<Vec<Transaction>>, StatusCode> {
-    let transactions = sqlx::query_as::<_, Transaction>(
+    Query(query): Query<TransactionListQuery>,
+) -> Result<Json<TransactionListResponse>, StatusCode> {
+    let limit = query.limit.unwrap_or(DEFAULT_TRANSACTION_LIMIT);
+    let offset = query.offset.unwrap_or(0);
+    let search = query.search.as_ref().map(|s| format!("%{}%", s));
+
+    let total = sqlx::query_scalar::<_, i64>(
+        "SELECT COUNT(*) FROM transactions
+         WHERE budget_id = ?
+           AND (? IS NULL OR date >= ?)
+           AND (? IS NULL OR date <= ?)
+           AND (? IS NULL OR category_id = ?)
+           AND (? IS NULL OR transaction_type = ?)
+           AND (? IS NULL OR amount >= ?)
+           AND (? IS NULL OR amount <= ?)
+           AND (? IS NULL OR title LIKE ? OR comment LIKE ?)"
+    )
+        .bind(&budget_id)
+        .bind(&query.since)
+        .bind(&query.since)
+        .bind(&query.until)
+        .bind(&query.until)
+        .bind(&query.category_id)
+        .bind(&query.category_id)
+        .bind(&query.transaction_type)
+        .bind(&query.transaction_type)
+        .bind(query.min_amount)
+        .bind(query.min_amount)
+        .bind(query.max_amount)
+        .bind(query.max_amount)
+        .bind(&search)
+        .bind(&search)
+        .bind(&search)
+        .fetch_one(pool.as_ref())
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to count transactions: {:?}", e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    let items = sqlx::query_as::<_, Transaction>(
         "SELECT id, budget_id, category_id, title, amount, transaction_type, date, comment,
-         is_recurring, created_at FROM transactions WHERE budget_id = ? ORDER BY date DESC"
+         is_recurring, created_at, import_id
+         FROM transactions
+         WHERE budget_id = ?
+           AND (? IS NULL OR date >= ?)
+           AND (? IS NULL OR date <= ?)
+           AND (? IS NULL OR category_id = ?)
+           AND (? IS NULL OR transaction_type = ?)
+           AND (? IS NULL OR amount >= ?)
+           AND (? IS NULL OR amount <= ?)
+           AND (? IS NULL OR title LIKE ? OR comment LIKE ?)
+         ORDER BY date DESC, id DESC
+         LIMIT ? OFFSET ?"
     )
         .bind(&budget_id)
+        .bind(&query.since)
+        .bind(&query.since)
+        .bind(&query.until)
+        .bind(&query.until)
+        .bind(&query.category_id)
+        .bind(&query.category_id)
+        .bind(&query.transaction_type)
+        .bind(&query.transaction_type)
+        .bind(query.min_amount)
+        .bind(query.min_amount)
+        .bind(query.max_amount)
+        .bind(query.max_amount)
+        .bind(&search)
+        .bind(&search)
+        .bind(&search)
+        .bind(limit)
+        .bind(offset)
         .fetch_all(pool.as_ref())
         .await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        .map_err(|e| {
+            tracing::error!("Failed to fetch transactions: {:?}", e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
 
-    Ok(Json(transactions))
+    Ok(Json(TransactionListResponse { items, total, limit, offset }))
 }
 
 /// Creates a new transaction.
@@ -89,6 +193,12 @@ pub async fn get_transactions(
 /// # Returns
 /// - `200 OK` with the created `Transaction` object
 /// - `500 Internal Server Error` if creation fails
+///
+/// # Notes
+/// For expense transactions, this also recomputes any budget alert
+/// (`budget_alerts`) scoped to the transaction's budget/category and
+/// triggers it if the period's spend now meets its threshold; see
+/// `budget_alerts::evaluate`.
 #[utoipa::path(
     post,
     path = "/api/budgets/{budget_id}/transactions",
@@ -101,42 +211,193 @@ pub async fn get_transactions(
 )]
 pub async fn create_transaction(
     State(pool): State<Arc<DbPool>>,
+    State(events): State<EventRegistry>,
     Json(payload): Json<CreateTransaction>,
 ) -> Result<Json<Transaction>, StatusCode> {
     let id = Uuid::new_v4().to_string();
     let now = Utc::now().to_rfc3339();
 
-    sqlx::query(
-        "INSERT INTO transactions (id, budget_id, category_id, title, amount, transaction_type,
-         date, comment, is_recurring, created_at)
-         VALUES (?, ?, ?, ?, ?, ?, ?, ?, 0, ?)"
-    )
-        .bind(&id)
-        .bind(&payload.budget_id)
-        .bind(&payload.category_id)
-        .bind(&payload.title)
-        .bind(payload.amount)
-        .bind(&payload.transaction_type)
-        .bind(&payload.date)
-        .bind(&payload.comment)
-        .bind(&now)
-        .execute(pool.as_ref())
-        .await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let transaction = crate::db::with_transaction(&pool, |tx| async {
+        sqlx::query(
+            "INSERT INTO transactions (id, budget_id, category_id, title, amount, transaction_type,
+             date, comment, is_recurring, created_at, import_id)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, 0, ?, ?)"
+        )
+            .bind(&id)
+            .bind(&payload.budget_id)
+            .bind(&payload.category_id)
+            .bind(&payload.title)
+            .bind(payload.amount)
+            .bind(&payload.transaction_type)
+            .bind(&payload.date)
+            .bind(&payload.comment)
+            .bind(&now)
+            .bind(&payload.import_id)
+            .execute(&mut **tx)
+            .await?;
 
-    let transaction = sqlx::query_as::<_, Transaction>(
-        "SELECT id, budget_id, category_id, title, amount, transaction_type, date, comment,
-         is_recurring, created_at FROM transactions WHERE id = ?"
-    )
-        .bind(&id)
-        .fetch_one(pool.as_ref())
+        sqlx::query_as::<_, Transaction>(
+            "SELECT id, budget_id, category_id, title, amount, transaction_type, date, comment,
+             is_recurring, created_at, import_id FROM transactions WHERE id = ?"
+        )
+            .bind(&id)
+            .fetch_one(&mut **tx)
+            .await
+    })
         .await
         .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
 
+    if payload.transaction_type == "expense" {
+        if let Err(e) = crate::budget_alerts::evaluate(&pool, &events, &payload.budget_id, &payload.category_id).await {
+            tracing::error!("Failed to evaluate budget alerts: {:?}", e);
+        }
+    }
+
     Ok(Json(transaction))
 }
 
-/// Deletes a transaction.
+/// Imports a batch of transactions for a budget in a single database
+/// transaction, e.g. from a bank CSV/OFX export.
+///
+/// # Endpoint
+/// `POST /api/budgets/:budget_id/transactions/bulk`
+///
+/// # Authentication
+/// Requires a valid JWT token. User must be a member of the budget.
+///
+/// # Path Parameters
+/// - `budget_id`: The budget's unique identifier
+///
+/// # Request Body
+/// An array of `CreateTransaction` payloads. Each may carry an `import_id`;
+/// a row whose `import_id` already exists for this budget is skipped as a
+/// duplicate rather than inserted again, so re-uploading the same file
+/// doesn't double-post.
+///
+/// # Returns
+/// - `200 OK` with a `BulkImportResponse` summarizing created IDs and
+///   skipped duplicate `import_id`s
+/// - `400 Bad Request` with a `BulkImportResponse` whose `errors` names the
+///   row that failed, if any row failed to insert; the whole batch is
+///   rolled back, so nothing is partially imported
+/// - `403 Forbidden` if the user is not a member of the budget
+/// - `500 Internal Server Error` if the transaction couldn't be committed
+#[utoipa::path(
+    post,
+    path = "/api/budgets/{budget_id}/transactions/bulk",
+    tag = "transactions",
+    params(
+        ("budget_id" = String, Path, description = "Budget unique identifier")
+    ),
+    request_body = Vec<CreateTransaction>,
+    responses(
+        (status = 200, description = "Import summary", body = BulkImportResponse),
+        (status = 400, description = "A row failed to insert; the batch was rolled back", body = BulkImportResponse),
+        (status = 403, description = "Caller is not a member of the budget"),
+        (status = 500, description = "Failed to commit the import")
+    )
+)]
+pub async fn bulk_import_transactions(
+    State(pool): State<Arc<DbPool>>,
+    State(events): State<EventRegistry>,
+    membership: BudgetMembership,
+    Path(budget_id): Path<String>,
+    Json(payload): Json<Vec<CreateTransaction>>,
+) -> Result<Json<BulkImportResponse>, (StatusCode, Json<BulkImportResponse>)> {
+    membership.require_role("member").map_err(|status| {
+        (status, Json(BulkImportResponse { created: vec![], duplicates: vec![], errors: vec![] }))
+    })?;
+
+    let mut expense_categories: std::collections::HashSet<String> = std::collections::HashSet::new();
+    let mut failed_row: Option<BulkImportError> = None;
+
+    let result = crate::db::with_transaction(&pool, |tx| async {
+        let mut created = Vec::new();
+        let mut duplicates = Vec::new();
+
+        for (index, item) in payload.into_iter().enumerate() {
+            if let Some(import_id) = &item.import_id {
+                let exists = sqlx::query_scalar::<_, i64>(
+                    "SELECT COUNT(*) FROM transactions WHERE budget_id = ? AND import_id = ?"
+                )
+                    .bind(&budget_id)
+                    .bind(import_id)
+                    .fetch_one(&mut **tx)
+                    .await?;
+
+                if exists > 0 {
+                    duplicates.push(import_id.clone());
+                    continue;
+                }
+            }
+
+            let id = Uuid::new_v4().to_string();
+            let now = Utc::now().to_rfc3339();
+
+            sqlx::query(
+                "INSERT INTO transactions (id, budget_id, category_id, title, amount, transaction_type,
+                 date, comment, is_recurring, created_at, import_id)
+                 VALUES (?, ?, ?, ?, ?, ?, ?, ?, 0, ?, ?)"
+            )
+                .bind(&id)
+                .bind(&budget_id)
+                .bind(&item.category_id)
+                .bind(&item.title)
+                .bind(item.amount)
+                .bind(&item.transaction_type)
+                .bind(&item.date)
+                .bind(&item.comment)
+                .bind(&now)
+                .bind(&item.import_id)
+                .execute(&mut **tx)
+                .await
+                .map_err(|e| {
+                    failed_row = Some(BulkImportError { index, message: e.to_string() });
+                    e
+                })?;
+
+            if item.transaction_type == "expense" {
+                expense_categories.insert(item.category_id.clone());
+            }
+            created.push(id);
+        }
+
+        Ok((created, duplicates))
+    })
+        .await;
+
+    let (created, duplicates) = match result {
+        Ok(value) => value,
+        Err(e) => {
+            return match failed_row {
+                Some(error) => {
+                    tracing::warn!("Bulk import row {} failed, rolling back batch: {}", error.index, error.message);
+                    Err((
+                        StatusCode::BAD_REQUEST,
+                        Json(BulkImportResponse { created: vec![], duplicates: vec![], errors: vec![error] }),
+                    ))
+                }
+                None => {
+                    tracing::error!("Bulk import transaction failed: {:?}", e);
+                    Err((
+                        StatusCode::INTERNAL_SERVER_ERROR,
+                        Json(BulkImportResponse { created: vec![], duplicates: vec![], errors: vec![] }),
+                    ))
+                }
+            };
+        }
+    };
+
+    for category_id in &expense_categories {
+        if let Err(e) = crate::budget_alerts::evaluate(&pool, &events, &budget_id, category_id).await {
+            tracing::error!("Failed to evaluate budget alerts: {:?}", e);
+        }
+    }
+
+    Ok(Json(BulkImportResponse { created, duplicates, errors: vec![] }))
+}
+
+/// Deletes a transaction, along with any receipt attachments on it.
 ///
 /// # Endpoint
 /// `DELETE /api/transactions/:id`
@@ -147,6 +408,13 @@ pub async fn create_transaction(
 /// # Returns
 /// - `204 No Content` on successful deletion
 /// - `500 Internal Server Error` if deletion fails
+///
+/// # Notes
+/// Stored attachment file content is removed first on a best-effort basis
+/// (see `handlers::attachments::delete_attachments_for_transaction`), then
+/// the attachment rows and the transaction row are deleted together in one
+/// `db::with_transaction`, so a deleted transaction never leaves orphaned
+/// attachment rows behind even if the delete fails partway through.
 #[utoipa::path(
     delete,
     path = "/api/transactions/{id}",
@@ -161,11 +429,21 @@ pub async fn create_transaction(
 )]
 pub async fn delete_transaction(
     State(pool): State<Arc<DbPool>>,
+    State(storage): State<Arc<dyn crate::storage::Storage>>,
     Path(id): Path<String>,
 ) -> Result<StatusCode, StatusCode> {
-    sqlx::query("DELETE FROM transactions WHERE id = ?")
-        .bind(&id)
-        .execute(pool.as_ref())
+    crate::handlers::attachments::delete_attachments_for_transaction(&pool, storage.as_ref(), &id).await?;
+
+    crate::db::with_transaction(&pool, |tx| async {
+        crate::handlers::attachments::delete_attachment_rows_for_transaction(tx, &id).await?;
+
+        sqlx::query("DELETE FROM transactions WHERE id = ?")
+            .bind(&id)
+            .execute(&mut **tx)
+            .await?;
+
+        Ok(())
+    })
         .await
         .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
 
@@ -201,7 +479,7 @@ pub async fn get_recurring_transactions(
 ) -> Result<Json<Vec<RecurringTransaction>>, StatusCode> {
     let transactions = sqlx::query_as::<_, RecurringTransaction>(
         "SELECT id, budget_id, category_id, title, amount, transaction_type, frequency, day,
-         active, created_at FROM recurring_transactions WHERE budget_id = ?"
+         active, created_at, next_run FROM recurring_transactions WHERE budget_id = ?"
     )
         .bind(&budget_id)
         .fetch_all(pool.as_ref())
@@ -245,30 +523,32 @@ pub async fn create_recurring_transaction(
     let id = Uuid::new_v4().to_string();
     let now = Utc::now().to_rfc3339();
 
-    sqlx::query(
-        "INSERT INTO recurring_transactions (id, budget_id, category_id, title, amount,
-         transaction_type, frequency, day, active, created_at)
-         VALUES (?, ?, ?, ?, ?, ?, ?, ?, 1, ?)"
-    )
-        .bind(&id)
-        .bind(&payload.budget_id)
-        .bind(&payload.category_id)
-        .bind(&payload.title)
-        .bind(payload.amount)
-        .bind(&payload.transaction_type)
-        .bind(&payload.frequency)
-        .bind(payload.day)
-        .bind(&now)
-        .execute(pool.as_ref())
-        .await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let transaction = crate::db::with_transaction(&pool, |tx| async {
+        sqlx::query(
+            "INSERT INTO recurring_transactions (id, budget_id, category_id, title, amount,
+             transaction_type, frequency, day, active, created_at)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, 1, ?)"
+        )
+            .bind(&id)
+            .bind(&payload.budget_id)
+            .bind(&payload.category_id)
+            .bind(&payload.title)
+            .bind(payload.amount)
+            .bind(&payload.transaction_type)
+            .bind(&payload.frequency)
+            .bind(payload.day)
+            .bind(&now)
+            .execute(&mut **tx)
+            .await?;
 
-    let transaction = sqlx::query_as::<_, RecurringTransaction>(
-        "SELECT id, budget_id, category_id, title, amount, transaction_type, frequency, day,
-         active, created_at FROM recurring_transactions WHERE id = ?"
-    )
-        .bind(&id)
-        .fetch_one(pool.as_ref())
+        sqlx::query_as::<_, RecurringTransaction>(
+            "SELECT id, budget_id, category_id, title, amount, transaction_type, frequency, day,
+             active, created_at, next_run FROM recurring_transactions WHERE id = ?"
+        )
+            .bind(&id)
+            .fetch_one(&mut **tx)
+            .await
+    })
         .await
         .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
 
@@ -307,7 +587,7 @@ pub async fn toggle_recurring_transaction(
     // Get the current state
     let current = sqlx::query_as::<_, RecurringTransaction>(
         "SELECT id, budget_id, category_id, title, amount, transaction_type, frequency, day,
-         active, created_at FROM recurring_transactions WHERE id = ?"
+         active, created_at, next_run FROM recurring_transactions WHERE id = ?"
     )
         .bind(&id)
         .fetch_one(pool.as_ref())
@@ -317,20 +597,21 @@ pub async fn toggle_recurring_transaction(
     // Toggle the state
     let new_active = !current.active;
 
-    sqlx::query("UPDATE recurring_transactions SET active = ? WHERE id = ?")
-        .bind(new_active)
-        .bind(&id)
-        .execute(pool.as_ref())
-        .await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let updated = crate::db::with_transaction(&pool, |tx| async {
+        sqlx::query("UPDATE recurring_transactions SET active = ? WHERE id = ?")
+            .bind(new_active)
+            .bind(&id)
+            .execute(&mut **tx)
+            .await?;
 
-    // Get the updated version
-    let updated = sqlx::query_as::<_, RecurringTransaction>(
-        "SELECT id, budget_id, category_id, title, amount, transaction_type, frequency, day,
-         active, created_at FROM recurring_transactions WHERE id = ?"
-    )
-        .bind(&id)
-        .fetch_one(pool.as_ref())
+        sqlx::query_as::<_, RecurringTransaction>(
+            "SELECT id, budget_id, category_id, title, amount, transaction_type, frequency, day,
+             active, created_at, next_run FROM recurring_transactions WHERE id = ?"
+        )
+            .bind(&id)
+            .fetch_one(&mut **tx)
+            .await
+    })
         .await
         .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
 
@@ -371,4 +652,160 @@ pub async fn delete_recurring_transaction(
         .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
 
     Ok(StatusCode::NO_CONTENT)
+}
+
+/// Forces generation of concrete transactions for every active recurring
+/// template whose `next_run` has passed.
+///
+/// # Endpoint
+/// `POST /api/recurring/run`
+///
+/// # Authentication
+/// Requires a valid JWT token with the global "admin" role.
+///
+/// # Returns
+/// - `200 OK` with the number of transactions created
+/// - `403 Forbidden` if the caller is not an admin
+/// - `500 Internal Server Error` if generation fails
+#[utoipa::path(
+    post,
+    path = "/api/recurring/run",
+    tag = "transactions",
+    responses(
+        (status = 200, description = "Generation run completed", body = RunRecurringResponse),
+        (status = 403, description = "Caller is not an admin"),
+        (status = 500, description = "Failed to generate recurring transactions")
+    )
+)]
+pub async fn run_recurring_transactions(
+    State(pool): State<Arc<DbPool>>,
+    auth: AuthUser,
+) -> Result<Json<RunRecurringResponse>, StatusCode> {
+    if auth.role != "admin" {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    let created = recurring::run_due(&pool, Utc::now().date_naive())
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to generate recurring transactions: {:?}", e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    Ok(Json(RunRecurringResponse { created }))
+}
+
+/// Runs generation of concrete transactions for a single recurring
+/// template on demand. Only occurrences on or before today are posted, so a
+/// template whose `next_run` is still in the future creates none. Intended
+/// for testing and manual backfills rather than routine use, since the
+/// hourly sweep already covers normal operation.
+///
+/// # Endpoint
+/// `POST /api/recurring/:id/run`
+///
+/// # Authentication
+/// Requires a valid JWT token with the global "admin" role, same as
+/// [`run_recurring_transactions`].
+///
+/// # Path Parameters
+/// - `id`: The recurring transaction's unique identifier
+///
+/// # Returns
+/// - `200 OK` with the number of transactions created
+/// - `403 Forbidden` if the caller is not an admin
+/// - `500 Internal Server Error` if generation fails
+#[utoipa::path(
+    post,
+    path = "/api/recurring/{id}/run",
+    tag = "transactions",
+    params(
+        ("id" = String, Path, description = "Recurring transaction unique identifier")
+    ),
+    responses(
+        (status = 200, description = "Generation run completed", body = RunRecurringResponse),
+        (status = 403, description = "Caller is not an admin"),
+        (status = 500, description = "Failed to generate recurring transaction")
+    )
+)]
+pub async fn run_recurring_transaction(
+    State(pool): State<Arc<DbPool>>,
+    auth: AuthUser,
+    Path(id): Path<String>,
+) -> Result<Json<RunRecurringResponse>, StatusCode> {
+    if auth.role != "admin" {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    let created = recurring::run_one(&pool, &id, Utc::now().date_naive())
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to generate recurring transaction {}: {:?}", id, e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    Ok(Json(RunRecurringResponse { created }))
+}
+
+/// Previews the occurrences a budget's recurring templates would post over
+/// an upcoming window, without inserting any transactions.
+///
+/// # Endpoint
+/// `GET /api/budgets/:budget_id/recurring/upcoming`
+///
+/// # Path Parameters
+/// - `budget_id`: The budget's unique identifier
+///
+/// # Query Parameters
+/// - `days`: (optional) Number of days ahead to preview, default 30
+///
+/// # Returns
+/// - `200 OK` with array of `UpcomingOccurrence`, sorted by date
+/// - `500 Internal Server Error` if the query fails
+#[utoipa::path(
+    get,
+    path = "/api/budgets/{budget_id}/recurring/upcoming",
+    tag = "transactions",
+    params(
+        ("budget_id" = String, Path, description = "Budget unique identifier"),
+        ("days" = Option<i64>, Query, description = "Number of days ahead to preview, default 30")
+    ),
+    responses(
+        (status = 200, description = "Upcoming occurrences", body = Vec<UpcomingOccurrence>),
+        (status = 500, description = "Failed to compute upcoming occurrences")
+    )
+)]
+pub async fn get_upcoming_recurring(
+    State(pool): State<Arc<DbPool>>,
+    Path(budget_id): Path<String>,
+    Query(query): Query<UpcomingQuery>,
+) -> Result<Json<Vec<UpcomingOccurrence>>, StatusCode> {
+    let days = query.days.unwrap_or(DEFAULT_UPCOMING_DAYS);
+
+    let templates = sqlx::query_as::<_, RecurringTransaction>(
+        "SELECT id, budget_id, category_id, title, amount, transaction_type, frequency, day,
+         active, created_at, next_run FROM recurring_transactions WHERE budget_id = ? AND active = 1"
+    )
+        .bind(&budget_id)
+        .fetch_all(pool.as_ref())
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let now = Utc::now().date_naive();
+
+    let mut occurrences: Vec<UpcomingOccurrence> = templates
+        .iter()
+        .flat_map(|template| {
+            recurring::upcoming_occurrences(template, now, days)
+                .into_iter()
+                .map(|date| UpcomingOccurrence {
+                    recurring_transaction_id: template.id.clone(),
+                    date: date.to_string(),
+                })
+        })
+        .collect();
+
+    occurrences.sort_by(|a, b| a.date.cmp(&b.date));
+
+    Ok(Json(occurrences))
 }
\ No newline at end of file
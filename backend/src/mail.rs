@@ -0,0 +1,175 @@
+// Copyright (c) 2024 Tempo Budget
+// SPDX-License-Identifier: MIT
+//
+// Outbound email delivery subsystem.
+
+//! # Mail Module
+//!
+//! Budget invitations need to notify an invitee by email. Rather than block
+//! a request handler on an SMTP round trip, this module runs a small actor:
+//! [`spawn_mail_actor`] starts a background task owning the `Mailer` and
+//! draining an mpsc channel, and [`enqueue`] is the fire-and-forget entry
+//! point handlers call to send a message through it. [`enqueue_html`] is the
+//! same thing for callers (like `reports`) that have a rendered HTML body
+//! to send alongside the plain-text one.
+
+use std::sync::OnceLock;
+use tokio::sync::mpsc;
+
+use crate::config::SmtpConfig;
+
+/// A message queued for delivery by the mail actor.
+#[derive(Debug)]
+pub struct MailMessage {
+    /// Recipient email address
+    pub to: String,
+    /// Email subject line
+    pub subject: String,
+    /// Plain-text email body
+    pub body: String,
+    /// Optional HTML alternative, e.g. for a rendered report. When present,
+    /// the SMTP mailer sends a `text/plain` + `text/html` multipart
+    /// alternative instead of a plain-text-only message.
+    pub html_body: Option<String>,
+}
+
+/// Sends an already-composed email. Implemented by the production SMTP
+/// mailer and (in environments without SMTP configured) a mailer that logs
+/// instead of sending, so local development never requires real credentials.
+trait Mailer: Send + Sync {
+    fn send(&self, message: &MailMessage) -> Result<(), String>;
+}
+
+/// Sends mail via SMTP using credentials from `Config::smtp`.
+struct SmtpMailer {
+    host: String,
+    port: u16,
+    username: String,
+    password: String,
+    from: String,
+}
+
+impl SmtpMailer {
+    /// Builds a mailer from the configured SMTP settings, or `None` if any
+    /// of them are unset (in which case [`spawn_mail_actor`] falls back to
+    /// [`LoggingMailer`]).
+    fn from_config(config: &SmtpConfig) -> Option<Self> {
+        Some(Self {
+            host: config.host.clone()?,
+            port: config.port?,
+            username: config.username.clone()?,
+            password: config.password.clone()?,
+            from: config.from.clone()?,
+        })
+    }
+}
+
+impl Mailer for SmtpMailer {
+    fn send(&self, message: &MailMessage) -> Result<(), String> {
+        let builder = lettre::Message::builder()
+            .from(self.from.parse().map_err(|e| format!("invalid SMTP_FROM: {e}"))?)
+            .to(message.to.parse().map_err(|e| format!("invalid recipient address: {e}"))?)
+            .subject(&message.subject);
+
+        let email = match &message.html_body {
+            Some(html) => builder
+                .multipart(lettre::message::MultiPart::alternative_plain_html(
+                    message.body.clone(),
+                    html.clone(),
+                ))
+                .map_err(|e| format!("failed to build email: {e}"))?,
+            None => builder
+                .body(message.body.clone())
+                .map_err(|e| format!("failed to build email: {e}"))?,
+        };
+
+        let creds = lettre::transport::smtp::authentication::Credentials::new(
+            self.username.clone(),
+            self.password.clone(),
+        );
+
+        let transport = lettre::SmtpTransport::relay(&self.host)
+            .map_err(|e| format!("failed to configure SMTP transport: {e}"))?
+            .port(self.port)
+            .credentials(creds)
+            .build();
+
+        lettre::Transport::send(&transport, &email)
+            .map(|_| ())
+            .map_err(|e| format!("failed to send email: {e}"))
+    }
+}
+
+/// Falls back to logging when SMTP isn't configured, so invitations still
+/// work (minus the notification) in local development.
+struct LoggingMailer;
+
+impl Mailer for LoggingMailer {
+    fn send(&self, message: &MailMessage) -> Result<(), String> {
+        tracing::info!(
+            "SMTP not configured; would have sent to {}: {} - {}",
+            message.to, message.subject, message.body
+        );
+        Ok(())
+    }
+}
+
+static MAIL_SENDER: OnceLock<mpsc::UnboundedSender<MailMessage>> = OnceLock::new();
+
+/// Starts the mail actor: a background task that owns the configured
+/// `Mailer` and processes queued messages one at a time. Call once at
+/// startup before any call to [`enqueue`].
+pub fn spawn_mail_actor(smtp: &SmtpConfig) {
+    let (tx, mut rx) = mpsc::unbounded_channel::<MailMessage>();
+
+    MAIL_SENDER.set(tx).expect("spawn_mail_actor called more than once");
+
+    let smtp = smtp.clone();
+    tokio::spawn(async move {
+        let mailer: Box<dyn Mailer> = match SmtpMailer::from_config(&smtp) {
+            Some(smtp) => Box::new(smtp),
+            None => Box::new(LoggingMailer),
+        };
+
+        while let Some(message) = rx.recv().await {
+            if let Err(e) = mailer.send(&message) {
+                tracing::error!("Failed to deliver email to {}: {}", message.to, e);
+            }
+        }
+    });
+}
+
+/// Queues an email for delivery without blocking the caller. A no-op (with
+/// a logged warning) if [`spawn_mail_actor`] hasn't run, which should only
+/// happen in tests that don't start the full application.
+pub fn enqueue(to: &str, subject: &str, body: &str) {
+    send(MailMessage {
+        to: to.to_string(),
+        subject: subject.to_string(),
+        body: body.to_string(),
+        html_body: None,
+    });
+}
+
+/// Like [`enqueue`], but also attaches an HTML alternative (see
+/// `reports::render`), so clients that render HTML show the formatted
+/// report instead of falling back to `text`.
+pub fn enqueue_html(to: &str, subject: &str, text: &str, html: &str) {
+    send(MailMessage {
+        to: to.to_string(),
+        subject: subject.to_string(),
+        body: text.to_string(),
+        html_body: Some(html.to_string()),
+    });
+}
+
+fn send(message: MailMessage) {
+    let to = message.to.clone();
+
+    match MAIL_SENDER.get() {
+        Some(sender) => {
+            let _ = sender.send(message);
+        }
+        None => tracing::warn!("Mail actor not started; dropping email to {}", to),
+    }
+}
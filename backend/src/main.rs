@@ -10,77 +10,182 @@
 //! the HTTP server with configured routes and middleware.
 
 mod auth;
+mod budget_alerts;
+mod config;
+mod db;
+mod error;
+mod events;
 mod handlers;
+mod mail;
 mod models;
 mod openapi;
+mod password;
+mod rate_limit;
+mod recurring;
+mod reports;
 mod routes;
+mod storage;
 
-use sqlx::sqlite::{SqlitePool, SqliteConnectOptions};
+use axum::extract::FromRef;
+use sqlx::sqlite::{SqlitePool, SqlitePoolOptions, SqliteConnectOptions};
 use dotenvy::dotenv;
-use std::{env, sync::Arc, str::FromStr};
-use tower_http::cors::{Any, CorsLayer};
+use std::{sync::Arc, str::FromStr};
+use tower_http::cors::{Any, AllowOrigin, CorsLayer};
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
+use crate::events::EventRegistry;
+
 /// Type alias for the SQLite connection pool used throughout the application.
 pub type DbPool = SqlitePool;
 
+/// Shared application state handed to the router.
+///
+/// Individual extractors (`State<Arc<DbPool>>`, the `AuthUser` and
+/// `BudgetMembership` guards) pull just the piece they need out of this via
+/// `FromRef`, so handlers written against `Arc<DbPool>` alone didn't need to
+/// change when `EventRegistry` was added.
+#[derive(Clone)]
+pub struct AppState {
+    pub pool: Arc<DbPool>,
+    pub events: EventRegistry,
+    pub storage: Arc<dyn storage::Storage>,
+}
+
+impl FromRef<AppState> for Arc<DbPool> {
+    fn from_ref(state: &AppState) -> Self {
+        state.pool.clone()
+    }
+}
+
+impl FromRef<AppState> for EventRegistry {
+    fn from_ref(state: &AppState) -> Self {
+        state.events.clone()
+    }
+}
+
+impl FromRef<AppState> for Arc<dyn storage::Storage> {
+    fn from_ref(state: &AppState) -> Self {
+        state.storage.clone()
+    }
+}
+
 /// Application entry point.
 ///
 /// Initializes the following components:
-/// - Environment variables from `.env` file
+/// - Environment variables from `.env` file, then typed config from
+///   `config.toml` + environment overrides (see `config.rs`)
 /// - Tracing/logging subscriber
 /// - SQLite database connection pool
-/// - Database schema
+/// - Database schema, via versioned migrations
 /// - HTTP server with CORS middleware
 #[tokio::main]
 async fn main() {
     // Load environment variables from .env file
     dotenv().ok();
 
+    // Load and validate typed configuration; panics with a field-specific
+    // message if something required is missing or malformed.
+    let config = config::load();
+
     // Initialize the logging subscriber
     tracing_subscriber::registry()
-        .with(tracing_subscriber::EnvFilter::new(
-            env::var("RUST_LOG").unwrap_or_else(|_| "info".into()),
-        ))
+        .with(tracing_subscriber::EnvFilter::new(config.log_level.clone()))
         .with(tracing_subscriber::fmt::layer())
         .init();
 
-    // Create the SQLx connection pool with create_if_missing option
-    let database_url = env::var("DATABASE_URL").unwrap_or_else(|_| "sqlite:budget.db".into());
+    auth::init_jwt_secret(config.jwt_secret.clone());
 
-    let connect_options = SqliteConnectOptions::from_str(&database_url)
-        .expect("Invalid DATABASE_URL")
+    // Create the SQLx connection pool with create_if_missing option
+    let connect_options = SqliteConnectOptions::from_str(&config.database.url)
+        .expect("Invalid database.url")
         .create_if_missing(true);
 
-    let pool = SqlitePool::connect_with(connect_options)
+    let pool = SqlitePoolOptions::new()
+        .max_connections(config.database.max_connections)
+        .connect_with(connect_options)
         .await
         .expect("Failed to connect to database");
 
     tracing::info!("Database pool created");
 
-    // Initialize the database schema
-    sqlx::query(&std::fs::read_to_string("schema.sql").expect("Failed to read schema.sql"))
-        .execute(&pool)
+    // Apply any migrations under migrations/ that haven't run against this
+    // database yet, tracking applied versions in sqlx's `_sqlx_migrations`
+    // table. Each file runs inside its own transaction, so a failed
+    // migration can't leave the schema half-applied.
+    sqlx::migrate!("./migrations")
+        .run(&pool)
         .await
-        .expect("Failed to initialize schema");
+        .expect("Failed to run database migrations");
 
-    tracing::info!("Database schema initialized");
+    tracing::info!("Database migrations applied");
 
     let pool = Arc::new(pool);
+    let storage: Arc<dyn storage::Storage> = Arc::from(storage::from_config(&config.storage));
+    let state = AppState { pool: pool.clone(), events: EventRegistry::default(), storage };
+
+    // Start the background mail actor used for invitation notifications
+    mail::spawn_mail_actor(&config.smtp);
+
+    // Periodically materialize due recurring transactions in the background
+    {
+        let pool = pool.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(std::time::Duration::from_secs(3600));
+            loop {
+                interval.tick().await;
+                match recurring::run_due(&pool, chrono::Utc::now().date_naive()).await {
+                    Ok(created) => tracing::info!("Recurring transaction sweep created {} transactions", created),
+                    Err(e) => tracing::error!("Recurring transaction sweep failed: {:?}", e),
+                }
+            }
+        });
+    }
+
+    // Periodically send any budget spending reports that have come due
+    {
+        let pool = pool.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(std::time::Duration::from_secs(3600));
+            loop {
+                interval.tick().await;
+                match reports::run_due(&pool, chrono::Utc::now()).await {
+                    Ok(sent) => tracing::info!("Report sweep sent {} budget reports", sent),
+                    Err(e) => tracing::error!("Report sweep failed: {:?}", e),
+                }
+            }
+        });
+    }
+
+    // Configure CORS middleware. An empty `allowed_origins` list keeps the
+    // permissive default this server has always shipped with.
+    let allow_origin = if config.cors.allowed_origins.is_empty() {
+        AllowOrigin::any()
+    } else {
+        let origins = config
+            .cors
+            .allowed_origins
+            .iter()
+            .map(|origin| origin.parse().expect("invalid cors.allowed_origins entry"))
+            .collect::<Vec<_>>();
+        AllowOrigin::list(origins)
+    };
 
-    // Configure CORS middleware
     let cors = CorsLayer::new()
-        .allow_origin(Any)
+        .allow_origin(allow_origin)
         .allow_methods(Any)
         .allow_headers(Any);
 
+    // Configure per-user/IP rate limiting
+    let rate_limiter = rate_limit::RateLimiter::from_config(&config.rate_limit);
+    rate_limiter.spawn_eviction_task();
+
     // Create the application router (includes Swagger UI)
-    let app = routes::create_router(pool)
-        .layer(cors);
-    
+    let app = routes::create_router(state)
+        .layer(cors)
+        .layer(rate_limit::RateLimitLayer::new(rate_limiter));
+
     // Start the HTTP server
-    let port = env::var("PORT").unwrap_or_else(|_| "3000".to_string());
-    let addr = format!("0.0.0.0:{}", port);
+    let addr = format!("{}:{}", config.server.bind, config.server.port);
 
     tracing::info!("Server starting on {}", addr);
 
@@ -88,7 +193,10 @@ async fn main() {
         .await
         .expect("Failed to bind");
 
-    axum::serve(listener, app)
-        .await
-        .expect("Server failed");
+    axum::serve(
+        listener,
+        app.into_make_service_with_connect_info::<std::net::SocketAddr>(),
+    )
+    .await
+    .expect("Server failed");
 }
\ No newline at end of file
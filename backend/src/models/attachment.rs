@@ -0,0 +1,34 @@
+// Copyright (c) 2024 Tempo Budget
+// SPDX-License-Identifier: MIT
+//
+// Transaction attachment (receipt) data models.
+
+//! # Attachment Models
+//!
+//! This module defines data structures for receipt files (images, PDFs)
+//! attached to a transaction. The file content itself lives behind the
+//! `Storage` trait (see `storage.rs`); these rows only index it.
+
+use serde::Serialize;
+use sqlx::FromRow;
+
+/// A receipt file attached to a transaction.
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct Attachment {
+    /// Unique identifier (UUID)
+    pub id: String,
+    /// ID of the transaction this attachment belongs to
+    pub transaction_id: String,
+    /// Original filename as uploaded
+    pub filename: String,
+    /// MIME type, e.g. "image/jpeg" or "application/pdf"
+    pub content_type: String,
+    /// Opaque key used to look the file up in the configured `Storage`
+    /// backend; not meaningful to API consumers
+    #[serde(skip_serializing)]
+    pub storage_key: String,
+    /// File size in bytes
+    pub size: i64,
+    /// Timestamp when the attachment was created (RFC 3339 format)
+    pub created_at: String,
+}
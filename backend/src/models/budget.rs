@@ -11,6 +11,8 @@
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 
+use crate::models::{Category, Transaction};
+
 /// Represents a budget in the system.
 ///
 /// A budget can be either personal (owned by a single user) or a group budget
@@ -51,4 +53,52 @@ pub struct UpdateBudget {
     pub name: Option<String>,
     /// New active status (0=inactive, 1=active)
     pub is_active: Option<i32>,
+}
+
+/// Query parameters accepted by `GET /api/budgets` for filtering and
+/// pagination.
+#[derive(Debug, Deserialize)]
+pub struct BudgetListQuery {
+    /// Maximum number of budgets to return (default 20)
+    pub limit: Option<i64>,
+    /// Number of budgets to skip (default 0)
+    pub offset: Option<i64>,
+    /// Filter by budget type: "personal" or "group"
+    pub budget_type: Option<String>,
+    /// Filter by active status (0=inactive, 1=active)
+    pub is_active: Option<i32>,
+}
+
+/// Paginated envelope returned by `GET /api/budgets`.
+#[derive(Debug, Serialize)]
+pub struct BudgetListResponse {
+    /// The page of budgets matching the query
+    pub items: Vec<Budget>,
+    /// Total number of budgets matching the filters, across all pages
+    pub total: i64,
+    /// The `limit` that was applied
+    pub limit: i64,
+    /// The `offset` that was applied
+    pub offset: i64,
+}
+
+/// Response payload for `POST /api/budgets/:id/share`.
+#[derive(Debug, Serialize)]
+pub struct ShareLinkResponse {
+    /// Path to the read-only snapshot, e.g. `/api/shared/<token>`
+    pub url: String,
+    /// Timestamp the link stops working (RFC 3339 format)
+    pub expires_at: String,
+}
+
+/// A read-only snapshot of a budget's current state, served to holders of a
+/// share link without requiring an account.
+#[derive(Debug, Serialize)]
+pub struct BudgetSnapshot {
+    /// The shared budget
+    pub budget: Budget,
+    /// The budget's categories
+    pub categories: Vec<Category>,
+    /// The budget's most recent transactions
+    pub recent_transactions: Vec<Transaction>,
 }
\ No newline at end of file
@@ -0,0 +1,61 @@
+// Copyright (c) 2024 Tempo Budget
+// SPDX-License-Identifier: MIT
+//
+// Budget alert (spending threshold) data models.
+
+//! # Budget Alert Models
+//!
+//! This module defines data structures for user-configured spending
+//! thresholds, optionally scoped to a single category within a budget.
+
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+/// A spending threshold on a budget, optionally scoped to one category.
+///
+/// `threshold_type` is either `"amount"` (an absolute spend) or
+/// `"percentage"` (a percentage of `limit_amount`). Once `triggered`, an
+/// alert stays triggered until deleted; it isn't automatically reset at the
+/// start of the next period.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct BudgetAlert {
+    /// Unique identifier (UUID)
+    pub id: String,
+    /// ID of the budget this alert watches
+    pub budget_id: String,
+    /// Category this alert is scoped to; `None` watches the whole budget
+    pub category_id: Option<String>,
+    /// "amount" or "percentage"
+    pub threshold_type: String,
+    /// Absolute amount, or a percentage (0-100) of `limit_amount`
+    pub threshold_value: f64,
+    /// The amount `threshold_value` is a percentage of; required when
+    /// `threshold_type` is "percentage"
+    pub limit_amount: Option<f64>,
+    /// Spending period label, e.g. "monthly". Evaluation always sums the
+    /// current calendar month, whatever this value says.
+    pub period: String,
+    /// Whether spend has crossed the threshold (0=no, 1=yes)
+    pub triggered: i32,
+    /// Timestamp the alert was triggered (RFC 3339 format), if it has been
+    pub triggered_at: Option<String>,
+    /// Timestamp when the alert was created (RFC 3339 format)
+    pub created_at: String,
+}
+
+/// Request payload for creating a budget alert.
+#[derive(Debug, Deserialize)]
+pub struct CreateBudgetAlert {
+    /// Category to scope this alert to; omit to watch the whole budget
+    pub category_id: Option<String>,
+    /// "amount" or "percentage"
+    pub threshold_type: String,
+    /// Absolute amount, or a percentage (0-100) of `limit_amount`
+    pub threshold_value: f64,
+    /// The amount `threshold_value` is a percentage of; required when
+    /// `threshold_type` is "percentage"
+    pub limit_amount: Option<f64>,
+    /// Spending period label. Defaults to "monthly"; other values are
+    /// stored as given but evaluated over the calendar month all the same.
+    pub period: Option<String>,
+}
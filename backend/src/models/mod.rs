@@ -12,12 +12,20 @@ pub mod user;
 pub mod budget;
 pub mod transaction;
 pub mod category;
+mod attachment;
+mod budget_alert;
 mod budget_member;
 mod invitation;
+mod refresh_token;
+mod report;
 
 pub use user::*;
 pub use budget::*;
 pub use transaction::*;
 pub use category::*;
+pub use attachment::*;
+pub use budget_alert::*;
 pub use budget_member::*;
-pub use invitation::*;
\ No newline at end of file
+pub use invitation::*;
+pub use refresh_token::*;
+pub use report::*;
\ No newline at end of file
@@ -0,0 +1,37 @@
+// Copyright (c) 2024 Tempo Budget
+// SPDX-License-Identifier: MIT
+//
+// Refresh token persistence model.
+
+//! # Refresh Token Models
+//!
+//! This module defines the database-backed refresh token record used by the
+//! two-token authentication scheme. Refresh tokens never appear in API
+//! responses in structured form (clients only ever see the opaque string),
+//! so this type carries no `ToSchema`/OpenAPI derive.
+
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+/// A persisted refresh token record.
+///
+/// Only `token_hash` is stored; the raw token is returned to the client once
+/// at issuance time and is never written to the database.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct RefreshToken {
+    /// Unique identifier (UUID)
+    pub id: String,
+    /// ID of the user this token was issued to
+    pub user_id: String,
+    /// SHA-256 hex digest of the raw refresh token
+    pub token_hash: String,
+    /// Unique identifier for this token, used for audit/logging; a rotated
+    /// token gets a fresh `jti`
+    pub jti: String,
+    /// Expiration timestamp (RFC 3339 format)
+    pub expires_at: String,
+    /// Whether the token has been revoked (0=active, 1=revoked)
+    pub revoked: i32,
+    /// Timestamp when the token was created (RFC 3339 format)
+    pub created_at: String,
+}
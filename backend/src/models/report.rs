@@ -0,0 +1,76 @@
+// Copyright (c) 2024 Tempo Budget
+// SPDX-License-Identifier: MIT
+//
+// Budget spending report data models.
+
+//! # Report Models
+//!
+//! This module defines data structures for the scheduled spending-summary
+//! emails configured per budget (see `reports.rs` for aggregation/rendering
+//! and `handlers::reports` for the HTTP endpoints).
+
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+/// A budget's report schedule.
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct BudgetReportSettings {
+    /// ID of the budget this schedule applies to
+    pub budget_id: String,
+    /// Whether scheduled report emails are sent (0=disabled, 1=enabled)
+    pub enabled: i32,
+    /// "weekly" or "monthly"
+    pub cadence: String,
+    /// Next time the scheduler will send a report (RFC 3339 format), `None`
+    /// while reports are disabled
+    pub next_send_at: Option<String>,
+    /// Timestamp these settings were last changed (RFC 3339 format)
+    pub updated_at: String,
+}
+
+/// Request payload for `PUT /api/budgets/:budget_id/report-settings`.
+#[derive(Debug, Deserialize)]
+pub struct UpdateReportSettings {
+    /// Whether scheduled report emails should be sent
+    pub enabled: bool,
+    /// "weekly" or "monthly"
+    pub cadence: String,
+}
+
+/// A category's totals within a report's period.
+#[derive(Debug, Serialize)]
+pub struct ReportCategoryTotal {
+    /// Category ID, or `None` for transactions with no category
+    pub category_id: Option<String>,
+    /// Category name, or `None` for transactions with no category
+    pub category_name: Option<String>,
+    /// Total income in this category for the period
+    pub income_total: f64,
+    /// Total expense in this category for the period
+    pub expense_total: f64,
+}
+
+/// A rendered spending summary for one budget/period, returned by
+/// `POST /api/budgets/:budget_id/report/preview` and emailed by the
+/// scheduler.
+#[derive(Debug, Serialize)]
+pub struct ReportPreview {
+    /// ID of the budget this report summarizes
+    pub budget_id: String,
+    /// "weekly" or "monthly"
+    pub cadence: String,
+    /// First date in the period (inclusive, `YYYY-MM-DD`)
+    pub period_start: String,
+    /// Last date in the period (inclusive, `YYYY-MM-DD`)
+    pub period_end: String,
+    /// Total income across the period
+    pub income_total: f64,
+    /// Total expense across the period
+    pub expense_total: f64,
+    /// Per-category breakdown
+    pub by_category: Vec<ReportCategoryTotal>,
+    /// Rendered HTML version of the report
+    pub html: String,
+    /// Rendered plain-text version of the report
+    pub text: String,
+}
@@ -34,6 +34,45 @@ pub struct Transaction {
     pub is_recurring: i32,
     /// Timestamp when the transaction was created (RFC 3339 format)
     pub created_at: String,
+    /// Client-supplied idempotency key used when this transaction was
+    /// created via `POST /api/budgets/:budget_id/transactions/bulk`
+    pub import_id: Option<String>,
+}
+
+/// Query parameters for `GET /api/budgets/:budget_id/transactions`.
+#[derive(Debug, Deserialize)]
+pub struct TransactionListQuery {
+    /// Only include transactions on or after this date (RFC 3339/ISO 8601)
+    pub since: Option<String>,
+    /// Only include transactions on or before this date (RFC 3339/ISO 8601)
+    pub until: Option<String>,
+    /// Filter by category
+    pub category_id: Option<String>,
+    /// Filter by "income" or "expense"
+    pub transaction_type: Option<String>,
+    /// Only include transactions with `amount >=` this value
+    pub min_amount: Option<f64>,
+    /// Only include transactions with `amount <=` this value
+    pub max_amount: Option<f64>,
+    /// Free-text search over `title` and `comment`
+    pub search: Option<String>,
+    /// Maximum number of transactions to return (default 50)
+    pub limit: Option<i64>,
+    /// Number of transactions to skip
+    pub offset: Option<i64>,
+}
+
+/// Paginated response envelope for `GET /api/budgets/:budget_id/transactions`.
+#[derive(Debug, Serialize)]
+pub struct TransactionListResponse {
+    /// The page of transactions matching the filters
+    pub items: Vec<Transaction>,
+    /// Total number of transactions matching the filters, ignoring pagination
+    pub total: i64,
+    /// Page size used for this response
+    pub limit: i64,
+    /// Offset used for this response
+    pub offset: i64,
 }
 
 /// Request payload for creating a new transaction.
@@ -53,6 +92,38 @@ pub struct CreateTransaction {
     pub date: String,
     /// Optional comment or note
     pub comment: Option<String>,
+    /// Client-supplied idempotency key. When creating via the bulk import
+    /// endpoint, a row whose `import_id` already exists for the budget is
+    /// skipped as a duplicate instead of inserted again.
+    pub import_id: Option<String>,
+}
+
+/// Summary returned by `POST /api/budgets/:budget_id/transactions/bulk`.
+///
+/// The import is all-or-nothing: every row is inserted in a single SQLite
+/// transaction, so if any row fails, the whole batch is rolled back. In
+/// that case `created` and `duplicates` are empty and `errors` carries the
+/// row that caused the rollback, returned alongside a `400` status rather
+/// than a `200`; a successful import always has an empty `errors`.
+#[derive(Debug, Serialize)]
+pub struct BulkImportResponse {
+    /// IDs of transactions that were created; empty if the import failed
+    pub created: Vec<String>,
+    /// `import_id`s that already existed for the budget and were skipped;
+    /// empty if the import failed
+    pub duplicates: Vec<String>,
+    /// The row that caused the batch to roll back, if any; 0-indexed into
+    /// the request array
+    pub errors: Vec<BulkImportError>,
+}
+
+/// One failed row from a bulk import request.
+#[derive(Debug, Serialize)]
+pub struct BulkImportError {
+    /// Index of the failed row in the request array
+    pub index: usize,
+    /// Description of the failure
+    pub message: String,
 }
 
 /// Represents a recurring transaction template.
@@ -81,6 +152,9 @@ pub struct RecurringTransaction {
     pub active: i32,
     /// Timestamp when the recurring transaction was created (RFC 3339 format)
     pub created_at: String,
+    /// Next date (YYYY-MM-DD) this template is due to post; `None` if it has
+    /// never run
+    pub next_run: Option<String>,
 }
 
 /// Request payload for creating a new recurring transaction.
@@ -100,4 +174,29 @@ pub struct CreateRecurringTransaction {
     pub frequency: String,
     /// Day of the period (optional, depends on frequency)
     pub day: Option<i32>,
+}
+
+/// Response payload for `POST /api/recurring/run` and
+/// `POST /api/recurring/:id/run`.
+#[derive(Debug, Serialize)]
+pub struct RunRecurringResponse {
+    /// Number of transactions created across the templates that were run
+    pub created: usize,
+}
+
+/// Query parameters for `GET /api/budgets/:budget_id/recurring/upcoming`.
+#[derive(Debug, Deserialize)]
+pub struct UpcomingQuery {
+    /// Number of days ahead to preview. Defaults to 30.
+    pub days: Option<i64>,
+}
+
+/// A single future occurrence of a recurring transaction, computed without
+/// inserting a transaction row or advancing the template's `next_run`.
+#[derive(Debug, Serialize)]
+pub struct UpcomingOccurrence {
+    /// ID of the recurring transaction template this occurrence belongs to
+    pub recurring_transaction_id: String,
+    /// Date the occurrence would post on (YYYY-MM-DD)
+    pub date: String,
 }
\ No newline at end of file
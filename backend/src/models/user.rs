@@ -29,6 +29,11 @@ pub struct User {
     pub avatar: Option<String>,
     /// Optional phone number
     pub phone: Option<String>,
+    /// Global site role (e.g. "user" or "admin"), distinct from a user's
+    /// per-budget membership role stored in `budget_members`
+    pub role: String,
+    /// Account status: "active" or "blocked"
+    pub status: String,
     /// Timestamp when the user was created (RFC 3339 format)
     pub created_at: String,
     /// Timestamp when the user was last updated (RFC 3339 format)
@@ -58,8 +63,26 @@ pub struct LoginRequest {
 /// Response payload for successful authentication.
 #[derive(Debug, Serialize)]
 pub struct AuthResponse {
-    /// JWT token for subsequent authenticated requests
-    pub token: String,
+    /// Short-lived JWT used to authenticate subsequent requests
+    pub access_token: String,
+    /// Long-lived opaque token used to obtain a new access token
+    pub refresh_token: String,
+    /// Number of seconds until `access_token` expires
+    pub expires_in: i64,
     /// The authenticated user's details
     pub user: User,
+}
+
+/// Request payload for refreshing an access token.
+#[derive(Debug, Deserialize)]
+pub struct RefreshRequest {
+    /// The refresh token previously issued to the client
+    pub refresh_token: String,
+}
+
+/// Request payload for logging out.
+#[derive(Debug, Deserialize)]
+pub struct LogoutRequest {
+    /// The refresh token to revoke
+    pub refresh_token: String,
 }
\ No newline at end of file
@@ -13,11 +13,13 @@ use utoipa::openapi::security::{HttpAuthScheme, HttpBuilder, SecurityScheme};
 
 use crate::models::{
     // User models
-    User, CreateUser, LoginRequest, AuthResponse,
+    User, CreateUser, LoginRequest, AuthResponse, RefreshRequest, LogoutRequest,
     // Budget models
-    Budget, CreateBudget, UpdateBudget,
+    Budget, CreateBudget, UpdateBudget, ShareLinkResponse, BudgetSnapshot, BudgetListResponse,
     // Transaction models
-    Transaction, CreateTransaction, RecurringTransaction, CreateRecurringTransaction,
+    Transaction, CreateTransaction, TransactionListResponse, BulkImportResponse,
+    BulkImportError, RecurringTransaction, CreateRecurringTransaction, RunRecurringResponse,
+    UpcomingOccurrence,
     // Category models
     Category, CreateCategory, UpdateCategory,
     BudgetProfile, BudgetProfileCategory, CreateBudgetProfile, CreateBudgetProfileCategory,
@@ -25,6 +27,12 @@ use crate::models::{
     BudgetMember, InviteMemberRequest, BudgetMemberWithUser,
     // Invitation models
     BudgetInvitation, BudgetInvitationWithDetails,
+    // Budget alert models
+    BudgetAlert, CreateBudgetAlert,
+    // Attachment models
+    Attachment,
+    // Report models
+    BudgetReportSettings, UpdateReportSettings, ReportPreview, ReportCategoryTotal,
 };
 
 use crate::handlers;
@@ -46,11 +54,15 @@ use crate::handlers;
         // Auth endpoints
         handlers::auth::register,
         handlers::auth::login,
+        handlers::auth::refresh,
+        handlers::auth::logout,
         // Budget endpoints
         handlers::budgets::get_budgets,
         handlers::budgets::create_budget,
         handlers::budgets::get_budget,
         handlers::budgets::delete_budget,
+        handlers::budgets::share_budget,
+        handlers::budgets::get_shared_budget,
         // Category endpoints
         handlers::categories::get_categories,
         handlers::categories::create_category,
@@ -59,11 +71,15 @@ use crate::handlers;
         // Transaction endpoints
         handlers::transactions::get_transactions,
         handlers::transactions::create_transaction,
+        handlers::transactions::bulk_import_transactions,
         handlers::transactions::delete_transaction,
         handlers::transactions::get_recurring_transactions,
         handlers::transactions::create_recurring_transaction,
         handlers::transactions::toggle_recurring_transaction,
         handlers::transactions::delete_recurring_transaction,
+        handlers::transactions::run_recurring_transactions,
+        handlers::transactions::run_recurring_transaction,
+        handlers::transactions::get_upcoming_recurring,
         // Budget members endpoints
         handlers::budget_members::get_budget_members,
         handlers::budget_members::invite_member,
@@ -72,15 +88,29 @@ use crate::handlers;
         handlers::invitations::get_my_invitations,
         handlers::invitations::accept_invitation,
         handlers::invitations::reject_invitation,
+        // Budget alert endpoints
+        handlers::budget_alerts::get_alerts,
+        handlers::budget_alerts::create_alert,
+        handlers::budget_alerts::delete_alert,
+        handlers::budget_alerts::get_alert_status,
+        // Attachment endpoints
+        handlers::attachments::upload_attachment,
+        handlers::attachments::get_attachments,
+        handlers::attachments::delete_attachment,
+        // Report endpoints
+        handlers::reports::update_report_settings,
+        handlers::reports::preview_report,
     ),
     components(
         schemas(
             // User schemas
-            User, CreateUser, LoginRequest, AuthResponse,
+            User, CreateUser, LoginRequest, AuthResponse, RefreshRequest, LogoutRequest,
             // Budget schemas
-            Budget, CreateBudget, UpdateBudget,
+            Budget, CreateBudget, UpdateBudget, ShareLinkResponse, BudgetSnapshot, BudgetListResponse,
             // Transaction schemas
-            Transaction, CreateTransaction, RecurringTransaction, CreateRecurringTransaction,
+            Transaction, CreateTransaction, TransactionListResponse, BulkImportResponse,
+            BulkImportError, RecurringTransaction,
+            CreateRecurringTransaction, RunRecurringResponse, UpcomingOccurrence,
             // Category schemas
             Category, CreateCategory, UpdateCategory,
             BudgetProfile, BudgetProfileCategory, CreateBudgetProfile, CreateBudgetProfileCategory,
@@ -88,6 +118,12 @@ use crate::handlers;
             BudgetMember, InviteMemberRequest, BudgetMemberWithUser,
             // Invitation schemas
             BudgetInvitation, BudgetInvitationWithDetails,
+            // Budget alert schemas
+            BudgetAlert, CreateBudgetAlert,
+            // Attachment schemas
+            Attachment,
+            // Report schemas
+            BudgetReportSettings, UpdateReportSettings, ReportPreview, ReportCategoryTotal,
         )
     ),
     tags(
@@ -97,6 +133,7 @@ use crate::handlers;
         (name = "transactions", description = "Transaction management endpoints"),
         (name = "members", description = "Budget member management endpoints"),
         (name = "invitations", description = "Invitation management endpoints"),
+        (name = "alerts", description = "Budget alert (spending threshold) endpoints"),
     ),
     modifiers(&SecurityAddon)
 )]
@@ -0,0 +1,70 @@
+// Copyright (c) 2024 Tempo Budget
+// SPDX-License-Identifier: MIT
+//
+// Algorithm-agile password hashing with transparent migration from bcrypt.
+
+//! # Password Module
+//!
+//! New passwords are always hashed with Argon2id, the current recommendation
+//! for memory-hard password storage. `verify` stays backward compatible with
+//! the bcrypt hashes this codebase used to produce by dispatching on the
+//! stored hash's prefix, so existing accounts keep working without a forced
+//! reset; `handlers::auth::login` uses a successful bcrypt verification as
+//! the signal to transparently rehash with Argon2id.
+
+use argon2::{
+    password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString},
+    Argon2, Params, Version,
+};
+use std::env;
+
+/// Reads an Argon2 cost parameter from the environment, falling back to
+/// `default` when unset or unparsable.
+fn env_param(key: &str, default: u32) -> u32 {
+    env::var(key)
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(default)
+}
+
+/// Builds an `Argon2` instance from env-configurable cost parameters.
+///
+/// - `ARGON2_MEMORY_KIB` - memory cost in KiB (default 19456, ~19 MiB)
+/// - `ARGON2_ITERATIONS` - time cost (default 2)
+/// - `ARGON2_PARALLELISM` - degree of parallelism (default 1)
+fn argon2() -> Argon2<'static> {
+    let memory_kib = env_param("ARGON2_MEMORY_KIB", 19456);
+    let iterations = env_param("ARGON2_ITERATIONS", 2);
+    let parallelism = env_param("ARGON2_PARALLELISM", 1);
+
+    let params = Params::new(memory_kib, iterations, parallelism, None)
+        .expect("invalid Argon2 cost parameters");
+
+    Argon2::new(argon2::Algorithm::Argon2id, Version::V0x13, params)
+}
+
+/// Hashes a plain-text password, producing an Argon2id PHC string
+/// (`$argon2id$v=19$...`).
+pub fn hash(plain: &str) -> Result<String, argon2::password_hash::Error> {
+    let salt = SaltString::generate(&mut rand::thread_rng());
+    let hash = argon2().hash_password(plain.as_bytes(), &salt)?;
+    Ok(hash.to_string())
+}
+
+/// Verifies a plain-text password against a stored hash, dispatching on the
+/// stored hash's prefix: `$2` for bcrypt, `$argon2` for Argon2.
+pub fn verify(plain: &str, stored: &str) -> Result<bool, argon2::password_hash::Error> {
+    if stored.starts_with("$2") {
+        return Ok(bcrypt::verify(plain, stored).unwrap_or(false));
+    }
+
+    let parsed = PasswordHash::new(stored)?;
+    Ok(argon2().verify_password(plain.as_bytes(), &parsed).is_ok())
+}
+
+/// Returns `true` if `stored` is a bcrypt hash rather than an Argon2 one,
+/// used by `login` to decide whether a successful verification should
+/// trigger a transparent rehash.
+pub fn needs_rehash(stored: &str) -> bool {
+    stored.starts_with("$2")
+}
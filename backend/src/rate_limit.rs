@@ -0,0 +1,182 @@
+// Copyright (c) 2024 Tempo Budget
+// SPDX-License-Identifier: MIT
+//
+// Per-user/IP token-bucket rate limiting middleware.
+
+//! # Rate Limit Module
+//!
+//! A tower layer applied around the whole router (see `main.rs`, next to the
+//! CORS layer) that throttles request bursts with an in-memory token
+//! bucket per key. The key is the authenticated user id when the request
+//! carries a valid `Authorization: Bearer` header, and the peer IP
+//! otherwise, so anonymous traffic (e.g. `login`, `register`) is still
+//! bounded.
+
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+
+use axum::extract::ConnectInfo;
+use axum::http::{Request, StatusCode};
+use axum::response::{IntoResponse, Response};
+use futures::future::BoxFuture;
+use tower::{Layer, Service};
+
+use crate::config::RateLimitConfig;
+
+/// How long a bucket can sit untouched before the eviction sweep reclaims
+/// its memory.
+const BUCKET_IDLE_TTL: Duration = Duration::from_secs(600);
+
+struct Bucket {
+    tokens: f64,
+    last_refill: Instant,
+}
+
+/// Shared token-bucket rate limiter state, cheap to clone (an `Arc` behind
+/// the scenes) so it can be handed to both the tower layer and the
+/// background eviction task.
+#[derive(Clone)]
+pub struct RateLimiter {
+    buckets: Arc<Mutex<HashMap<String, Bucket>>>,
+    /// Maximum tokens a bucket can hold (i.e. the size of an allowed burst)
+    capacity: f64,
+    /// Tokens restored per second
+    refill_per_sec: f64,
+}
+
+impl RateLimiter {
+    /// Builds a limiter from the configured capacity and refill rate.
+    pub fn from_config(config: &RateLimitConfig) -> Self {
+        Self {
+            buckets: Arc::new(Mutex::new(HashMap::new())),
+            capacity: config.capacity,
+            refill_per_sec: config.refill_per_sec,
+        }
+    }
+
+    /// Refills and attempts to spend one token for `key`. Returns `true` if
+    /// the request is allowed.
+    fn try_consume(&self, key: &str) -> bool {
+        let mut buckets = self.buckets.lock().expect("rate limiter mutex poisoned");
+        let now = Instant::now();
+
+        let bucket = buckets.entry(key.to_string()).or_insert_with(|| Bucket {
+            tokens: self.capacity,
+            last_refill: now,
+        });
+
+        let elapsed = now.duration_since(bucket.last_refill).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * self.refill_per_sec).min(self.capacity);
+        bucket.last_refill = now;
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Spawns a background task that periodically drops buckets idle for
+    /// longer than [`BUCKET_IDLE_TTL`], bounding memory use under a large
+    /// number of distinct callers.
+    pub fn spawn_eviction_task(&self) {
+        let buckets = self.buckets.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(BUCKET_IDLE_TTL);
+            loop {
+                interval.tick().await;
+                let mut buckets = buckets.lock().expect("rate limiter mutex poisoned");
+                buckets.retain(|_, bucket| bucket.last_refill.elapsed() < BUCKET_IDLE_TTL);
+            }
+        });
+    }
+}
+
+/// Extracts the rate-limit key for a request: the authenticated user id if
+/// the `Authorization` header carries a valid JWT, otherwise the peer IP
+/// (populated via `ConnectInfo`, falling back to `"unknown"`).
+fn rate_limit_key<B>(req: &Request<B>) -> String {
+    let bearer_user_id = req
+        .headers()
+        .get("Authorization")
+        .and_then(|h| h.to_str().ok())
+        .and_then(|h| h.strip_prefix("Bearer "))
+        .and_then(|token| crate::auth::verify_token(token).ok())
+        .map(|claims| claims.sub);
+
+    if let Some(user_id) = bearer_user_id {
+        return user_id;
+    }
+
+    req.extensions()
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|ConnectInfo(addr)| addr.ip().to_string())
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+/// Tower layer wrapping a service with the rate limiter.
+#[derive(Clone)]
+pub struct RateLimitLayer {
+    limiter: RateLimiter,
+}
+
+impl RateLimitLayer {
+    pub fn new(limiter: RateLimiter) -> Self {
+        Self { limiter }
+    }
+}
+
+impl<S> Layer<S> for RateLimitLayer {
+    type Service = RateLimitMiddleware<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RateLimitMiddleware { inner, limiter: self.limiter.clone() }
+    }
+}
+
+#[derive(Clone)]
+pub struct RateLimitMiddleware<S> {
+    inner: S,
+    limiter: RateLimiter,
+}
+
+impl<S, B> Service<Request<B>> for RateLimitMiddleware<S>
+where
+    S: Service<Request<B>, Response = Response> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+    B: Send + 'static,
+{
+    type Response = Response;
+    type Error = S::Error;
+    type Future = BoxFuture<'static, Result<Response, S::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request<B>) -> Self::Future {
+        let key = rate_limit_key(&req);
+        let allowed = self.limiter.try_consume(&key);
+
+        // Service::call requires &mut self; clone so the stored inner
+        // isn't left in a polled-but-unready state if this future is dropped.
+        let mut inner = self.inner.clone();
+
+        Box::pin(async move {
+            if allowed {
+                inner.call(req).await
+            } else {
+                Ok((
+                    StatusCode::TOO_MANY_REQUESTS,
+                    [("Retry-After", "1")],
+                    "Too many requests",
+                )
+                    .into_response())
+            }
+        })
+    }
+}
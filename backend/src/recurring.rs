@@ -0,0 +1,335 @@
+// Copyright (c) 2024 Tempo Budget
+// SPDX-License-Identifier: MIT
+//
+// Generation of concrete transactions from recurring transaction templates.
+
+//! # Recurring Transaction Generation
+//!
+//! `recurring_transactions` rows are templates; this module turns them into
+//! real `transactions` rows. Each template tracks `next_run`, the next date
+//! it's due to post. [`run_due`] catches up every active template whose
+//! `next_run` has passed, inserting one transaction per missed period so a
+//! server that was down for a while doesn't silently skip occurrences.
+//! [`run_one`] does the same for a single template on demand, and
+//! [`upcoming_occurrences`] previews future occurrences without posting
+//! anything.
+
+use chrono::{Datelike, Duration, NaiveDate};
+use uuid::Uuid;
+
+use crate::{models::RecurringTransaction, DbPool};
+
+/// Returns the last valid day of `year`-`month` (1-12), used to clamp
+/// monthly/yearly recurrences that fall on a day a short month doesn't have
+/// (e.g. day 31 in February).
+fn last_day_of_month(year: i32, month: u32) -> u32 {
+    let (next_year, next_month) = if month == 12 { (year + 1, 1) } else { (year, month + 1) };
+    NaiveDate::from_ymd_opt(next_year, next_month, 1)
+        .unwrap()
+        .pred_opt()
+        .unwrap()
+        .day()
+}
+
+/// Advances `date` by one occurrence of `template`'s frequency.
+fn advance(template: &RecurringTransaction, date: NaiveDate) -> NaiveDate {
+    match template.frequency.as_str() {
+        "daily" => date + Duration::days(1),
+        "weekly" => date + Duration::days(7),
+        "monthly" => {
+            let target_day = template.day.unwrap_or(date.day() as i32).clamp(1, 31) as u32;
+            let (year, month) = if date.month() == 12 { (date.year() + 1, 1) } else { (date.year(), date.month() + 1) };
+            let day = target_day.min(last_day_of_month(year, month));
+            NaiveDate::from_ymd_opt(year, month, day).unwrap()
+        }
+        "yearly" => {
+            let target_day = template.day.unwrap_or(date.day() as i32).clamp(1, 31) as u32;
+            let year = date.year() + 1;
+            let day = target_day.min(last_day_of_month(year, date.month()));
+            NaiveDate::from_ymd_opt(year, date.month(), day).unwrap()
+        }
+        _ => date + Duration::days(1),
+    }
+}
+
+/// Aligns `from` onto `template`'s target day-of-month, the same way the
+/// `weekly` branch of [`due_occurrences`] walks forward to the target
+/// weekday. Used for `monthly`/`yearly` templates, where the target day
+/// lives within `from`'s own month (for `monthly`) or `from`'s own
+/// month-and-year (for `yearly`, since `advance` only ever changes the
+/// year): if that day has already passed relative to `from`, the first
+/// occurrence is the *next* period's target day instead, via [`advance`].
+fn align_to_target_day(template: &RecurringTransaction, from: NaiveDate) -> NaiveDate {
+    let target_day = template.day.unwrap_or(from.day() as i32).clamp(1, 31) as u32;
+    let day = target_day.min(last_day_of_month(from.year(), from.month()));
+    let candidate = NaiveDate::from_ymd_opt(from.year(), from.month(), day).unwrap();
+
+    if candidate >= from {
+        candidate
+    } else {
+        advance(template, candidate)
+    }
+}
+
+/// Computes every occurrence date of `template` in the half-open range
+/// `[from, to)`, honoring its frequency:
+/// - `daily` - every day
+/// - `weekly` - each date whose weekday matches `template.day` (0 = Sunday)
+/// - `monthly` - the `day`-th of each month, clamped to the month's last day
+/// - `yearly` - the same month/day each year, with the same clamp
+pub fn due_occurrences(template: &RecurringTransaction, from: NaiveDate, to: NaiveDate) -> Vec<NaiveDate> {
+    let mut occurrences = Vec::new();
+
+    let mut cursor = match template.frequency.as_str() {
+        "weekly" => {
+            let target_weekday = template.day.unwrap_or(0).rem_euclid(7) as u32;
+            let mut date = from;
+            while date.weekday().num_days_from_sunday() != target_weekday {
+                date += Duration::days(1);
+            }
+            date
+        }
+        "monthly" | "yearly" => align_to_target_day(template, from),
+        _ => from,
+    };
+
+    while cursor < to {
+        occurrences.push(cursor);
+        cursor = advance(template, cursor);
+    }
+
+    occurrences
+}
+
+/// Generates concrete `transactions` rows for `template`'s occurrences up to
+/// and including `now`, advancing `next_run` past `now`. Idempotent: a
+/// template whose `next_run` is already past `now` has no due occurrences
+/// and is left untouched, so calling this twice for the same `now` only
+/// posts once. The inserts and the `next_run` advance all commit as one
+/// `db::with_transaction`, so a crash partway through a multi-occurrence
+/// catch-up can't post some transactions without moving `next_run` past
+/// them (which would silently drop the rest on the next sweep).
+///
+/// Returns the number of transactions created.
+async fn generate_for_template(
+    pool: &DbPool,
+    template: &RecurringTransaction,
+    now: NaiveDate,
+) -> Result<usize, sqlx::Error> {
+    let from = match &template.next_run {
+        Some(next_run) => NaiveDate::parse_from_str(next_run, "%Y-%m-%d").unwrap_or(now),
+        None => template.created_at[..10].parse().unwrap_or(now),
+    };
+
+    if from > now {
+        return Ok(0);
+    }
+
+    let to = now + Duration::days(1);
+    let occurrences = due_occurrences(template, from, to);
+
+    if occurrences.is_empty() {
+        return Ok(0);
+    }
+
+    let next_run = occurrences.last().map(|d| advance(template, *d)).unwrap_or(to);
+
+    crate::db::with_transaction(pool, |tx| async {
+        for date in &occurrences {
+            let id = Uuid::new_v4().to_string();
+            let created_at = chrono::Utc::now().to_rfc3339();
+
+            sqlx::query(
+                "INSERT INTO transactions (id, budget_id, category_id, title, amount,
+                 transaction_type, date, comment, is_recurring, created_at)
+                 VALUES (?, ?, ?, ?, ?, ?, ?, NULL, 1, ?)"
+            )
+                .bind(&id)
+                .bind(&template.budget_id)
+                .bind(&template.category_id)
+                .bind(&template.title)
+                .bind(template.amount)
+                .bind(&template.transaction_type)
+                .bind(date.to_string())
+                .bind(&created_at)
+                .execute(&mut **tx)
+                .await?;
+        }
+
+        sqlx::query("UPDATE recurring_transactions SET next_run = ? WHERE id = ?")
+            .bind(next_run.to_string())
+            .bind(&template.id)
+            .execute(&mut **tx)
+            .await?;
+
+        Ok(())
+    })
+        .await?;
+
+    Ok(occurrences.len())
+}
+
+/// Generates concrete `transactions` rows for every active template whose
+/// `next_run` is on or before `now`.
+///
+/// Returns the number of transactions created.
+pub async fn run_due(pool: &DbPool, now: NaiveDate) -> Result<usize, sqlx::Error> {
+    let templates = sqlx::query_as::<_, RecurringTransaction>(
+        "SELECT id, budget_id, category_id, title, amount, transaction_type, frequency, day,
+         active, created_at, next_run FROM recurring_transactions WHERE active = 1"
+    )
+        .fetch_all(pool)
+        .await?;
+
+    let mut created = 0usize;
+    for template in &templates {
+        created += generate_for_template(pool, template, now).await?;
+    }
+
+    Ok(created)
+}
+
+/// Runs generation for a single active template by id on demand, outside
+/// the hourly sweep (e.g. the `/api/recurring/:id/run` endpoint, used for
+/// testing). Only occurrences on or before `now` are posted, so a template
+/// whose `next_run` is still in the future gets none.
+///
+/// Returns the number of transactions created, or `Ok(0)` if no such active
+/// template exists.
+pub async fn run_one(pool: &DbPool, template_id: &str, now: NaiveDate) -> Result<usize, sqlx::Error> {
+    let template = sqlx::query_as::<_, RecurringTransaction>(
+        "SELECT id, budget_id, category_id, title, amount, transaction_type, frequency, day,
+         active, created_at, next_run FROM recurring_transactions WHERE id = ? AND active = 1"
+    )
+        .bind(template_id)
+        .fetch_optional(pool)
+        .await?;
+
+    match template {
+        Some(template) => generate_for_template(pool, &template, now).await,
+        None => Ok(0),
+    }
+}
+
+/// Previews occurrences `template` would post over the next `days` days
+/// from `now`, without inserting anything or advancing `next_run`.
+pub fn upcoming_occurrences(template: &RecurringTransaction, now: NaiveDate, days: i64) -> Vec<NaiveDate> {
+    let from = match &template.next_run {
+        Some(next_run) => NaiveDate::parse_from_str(next_run, "%Y-%m-%d").unwrap_or(now),
+        None => template.created_at[..10].parse().unwrap_or(now),
+    }
+    .max(now);
+
+    due_occurrences(template, from, now + Duration::days(days))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn template(frequency: &str, day: Option<i32>) -> RecurringTransaction {
+        RecurringTransaction {
+            id: "template-1".to_string(),
+            budget_id: "budget-1".to_string(),
+            category_id: "category-1".to_string(),
+            title: "Rent".to_string(),
+            amount: 100.0,
+            transaction_type: "expense".to_string(),
+            frequency: frequency.to_string(),
+            day,
+            active: 1,
+            created_at: "2024-01-03T00:00:00+00:00".to_string(),
+            next_run: None,
+        }
+    }
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn last_day_of_month_handles_leap_years() {
+        assert_eq!(last_day_of_month(2024, 2), 29); // 2024 is a leap year
+        assert_eq!(last_day_of_month(2023, 2), 28); // 2023 is not
+        assert_eq!(last_day_of_month(2024, 4), 30);
+        assert_eq!(last_day_of_month(2024, 12), 31);
+    }
+
+    #[test]
+    fn advance_monthly_clamps_day_31_to_short_months() {
+        let tmpl = template("monthly", Some(31));
+
+        // Jan 31 -> Feb should clamp to the 29th in a leap year.
+        assert_eq!(advance(&tmpl, date(2024, 1, 31)), date(2024, 2, 29));
+        // Feb 29 -> Mar rolls back up to the 31st once the month has one.
+        assert_eq!(advance(&tmpl, date(2024, 2, 29)), date(2024, 3, 31));
+    }
+
+    #[test]
+    fn advance_yearly_clamps_feb_29_in_non_leap_years() {
+        let tmpl = template("yearly", Some(29));
+
+        assert_eq!(advance(&tmpl, date(2024, 2, 29)), date(2025, 2, 28));
+    }
+
+    #[test]
+    fn due_occurrences_weekly_walks_to_target_weekday() {
+        // day=3 means Wednesday (0 = Sunday); from a Monday, the first
+        // occurrence should be two days later, then weekly after that.
+        let tmpl = template("weekly", Some(3));
+        let from = date(2024, 6, 3); // a Monday
+        let to = date(2024, 6, 20);
+
+        let occurrences = due_occurrences(&tmpl, from, to);
+
+        assert_eq!(occurrences, vec![date(2024, 6, 5), date(2024, 6, 12), date(2024, 6, 19)]);
+    }
+
+    #[test]
+    fn due_occurrences_monthly_aligns_first_occurrence_to_target_day() {
+        // Created the 3rd with day=15: the first occurrence should be the
+        // 15th of the same month, not the 3rd.
+        let tmpl = template("monthly", Some(15));
+        let from = date(2024, 1, 3);
+        let to = date(2024, 3, 1);
+
+        let occurrences = due_occurrences(&tmpl, from, to);
+
+        assert_eq!(occurrences, vec![date(2024, 1, 15), date(2024, 2, 15)]);
+    }
+
+    #[test]
+    fn due_occurrences_monthly_rolls_to_next_month_when_target_day_already_passed() {
+        // `from` is already past this month's target day, so the first
+        // occurrence should be next month's instead of a stale one.
+        let tmpl = template("monthly", Some(15));
+        let from = date(2024, 1, 20);
+        let to = date(2024, 3, 1);
+
+        let occurrences = due_occurrences(&tmpl, from, to);
+
+        assert_eq!(occurrences, vec![date(2024, 2, 15)]);
+    }
+
+    #[test]
+    fn due_occurrences_is_empty_past_the_end_of_the_range() {
+        let tmpl = template("daily", None);
+
+        assert!(due_occurrences(&tmpl, date(2024, 1, 10), date(2024, 1, 10)).is_empty());
+    }
+
+    #[test]
+    fn due_occurrences_catch_up_is_idempotent_across_runs() {
+        // Simulates two successive `generate_for_template` sweeps: the
+        // second sweep's `from` is the first sweep's last occurrence
+        // advanced past, so no occurrence is ever posted twice.
+        let tmpl = template("daily", None);
+
+        let first_run = due_occurrences(&tmpl, date(2024, 1, 1), date(2024, 1, 4));
+        let next_from = advance(&tmpl, *first_run.last().unwrap());
+        let second_run = due_occurrences(&tmpl, next_from, date(2024, 1, 6));
+
+        assert_eq!(first_run, vec![date(2024, 1, 1), date(2024, 1, 2), date(2024, 1, 3)]);
+        assert_eq!(second_run, vec![date(2024, 1, 4), date(2024, 1, 5)]);
+    }
+}
@@ -0,0 +1,263 @@
+// Copyright (c) 2024 Tempo Budget
+// SPDX-License-Identifier: MIT
+//
+// Scheduled budget spending-summary reports.
+
+//! # Reports Module
+//!
+//! `budget_report_settings` rows are a per-budget schedule (see
+//! `handlers::reports` for the `PUT .../report-settings` endpoint that
+//! manages them); this module is what actually builds and sends a report.
+//! [`build`] aggregates a budget's transactions for a period into a
+//! [`ReportPreview`] and renders it, and [`run_due`] is the scheduler sweep
+//! that sends one to every member of each budget whose `next_send_at` has
+//! passed, mirroring how `recurring::run_due` catches up due recurring
+//! transactions.
+
+use chrono::{Datelike, Duration, NaiveDate, Utc};
+
+use crate::{
+    mail,
+    models::{BudgetMemberWithUser, ReportCategoryTotal, ReportPreview},
+    DbPool,
+};
+
+/// Returns the inclusive `[start, end]` date range of the current period
+/// for `cadence`, as of `today`: the last 7 days for "weekly", or the
+/// current calendar month up to and including `today` for "monthly" (and
+/// anything else, since that's the more common cadence).
+pub fn period_bounds(cadence: &str, today: NaiveDate) -> (NaiveDate, NaiveDate) {
+    match cadence {
+        "weekly" => (today - Duration::days(6), today),
+        _ => (NaiveDate::from_ymd_opt(today.year(), today.month(), 1).unwrap(), today),
+    }
+}
+
+/// Returns the next time a report should be sent for `cadence`: seven days
+/// after `from` for "weekly", otherwise the first day of the month after
+/// `from`'s, at `from`'s time of day. Also used by `handlers::reports` to seed `next_send_at`
+/// when a budget first enables reports.
+pub fn next_send_at(cadence: &str, from: chrono::DateTime<Utc>) -> chrono::DateTime<Utc> {
+    match cadence {
+        "weekly" => from + Duration::days(7),
+        _ => {
+            let date = from.date_naive();
+            let (year, month) = if date.month() == 12 { (date.year() + 1, 1) } else { (date.year(), date.month() + 1) };
+            let next_month_start = NaiveDate::from_ymd_opt(year, month, 1).unwrap();
+            next_month_start.and_time(from.time()).and_utc()
+        }
+    }
+}
+
+/// Aggregates and renders a budget's spending summary for `cadence`'s
+/// current period (as of `today`).
+pub async fn build(pool: &DbPool, budget_id: &str, cadence: &str, today: NaiveDate) -> Result<ReportPreview, sqlx::Error> {
+    let (period_start, period_end) = period_bounds(cadence, today);
+
+    let budget_name = sqlx::query_scalar::<_, String>("SELECT name FROM budgets WHERE id = ?")
+        .bind(budget_id)
+        .fetch_one(pool)
+        .await?;
+
+    let by_category = sqlx::query_as::<_, (Option<String>, Option<String>, f64, f64)>(
+        "SELECT c.id, c.name,
+            COALESCE(SUM(CASE WHEN t.transaction_type = 'income' THEN t.amount ELSE 0 END), 0),
+            COALESCE(SUM(CASE WHEN t.transaction_type = 'expense' THEN t.amount ELSE 0 END), 0)
+         FROM transactions t
+         LEFT JOIN categories c ON t.category_id = c.id
+         WHERE t.budget_id = ? AND t.date >= ? AND t.date <= ?
+         GROUP BY c.id, c.name
+         ORDER BY c.name"
+    )
+        .bind(budget_id)
+        .bind(period_start.to_string())
+        .bind(period_end.to_string())
+        .fetch_all(pool)
+        .await?
+        .into_iter()
+        .map(|(category_id, category_name, income_total, expense_total)| ReportCategoryTotal {
+            category_id,
+            category_name,
+            income_total,
+            expense_total,
+        })
+        .collect::<Vec<_>>();
+
+    let income_total = by_category.iter().map(|c| c.income_total).sum();
+    let expense_total = by_category.iter().map(|c| c.expense_total).sum();
+
+    let (text, html) = render(&budget_name, cadence, period_start, period_end, income_total, expense_total, &by_category);
+
+    Ok(ReportPreview {
+        budget_id: budget_id.to_string(),
+        cadence: cadence.to_string(),
+        period_start: period_start.to_string(),
+        period_end: period_end.to_string(),
+        income_total,
+        expense_total,
+        by_category,
+        html,
+        text,
+    })
+}
+
+/// Escapes the characters that matter inside HTML text content (`&`, `<`,
+/// `>`, `"`, `'`), so user-controlled strings like a budget or category
+/// name can't inject markup into the report's `<h1>`/`<td>` cells.
+fn html_escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
+
+/// Renders a report's plain-text and HTML bodies from its aggregated
+/// totals. Kept deliberately simple (no templating engine) since the
+/// content is just a handful of numbers per category.
+fn render(
+    budget_name: &str,
+    cadence: &str,
+    period_start: NaiveDate,
+    period_end: NaiveDate,
+    income_total: f64,
+    expense_total: f64,
+    by_category: &[ReportCategoryTotal],
+) -> (String, String) {
+    let mut text = format!(
+        "{budget_name} - {cadence} report\n{period_start} to {period_end}\n\n\
+         Income: {income_total:.2}\nExpenses: {expense_total:.2}\nNet: {:.2}\n\nBy category:\n",
+        income_total - expense_total
+    );
+
+    let budget_name_html = html_escape(budget_name);
+    let mut html = format!(
+        "<h1>{budget_name_html} &mdash; {cadence} report</h1>\
+         <p>{period_start} to {period_end}</p>\
+         <p>Income: {income_total:.2}<br>Expenses: {expense_total:.2}<br>Net: {:.2}</p>\
+         <table border=\"1\" cellpadding=\"4\"><tr><th>Category</th><th>Income</th><th>Expense</th></tr>",
+        income_total - expense_total
+    );
+
+    for category in by_category {
+        let name = category.category_name.as_deref().unwrap_or("Uncategorized");
+        text.push_str(&format!("  {name}: income {:.2}, expense {:.2}\n", category.income_total, category.expense_total));
+        html.push_str(&format!(
+            "<tr><td>{}</td><td>{:.2}</td><td>{:.2}</td></tr>",
+            html_escape(name), category.income_total, category.expense_total
+        ));
+    }
+
+    html.push_str("</table>");
+
+    (text, html)
+}
+
+/// Emails `budget_id`'s current-period report to every member in
+/// `budget_members` (see `BudgetMemberWithUser`), so group budgets fan out
+/// to the whole group rather than just the owner.
+async fn send_report(pool: &DbPool, budget_id: &str, cadence: &str) -> Result<(), sqlx::Error> {
+    let report = build(pool, budget_id, cadence, Utc::now().date_naive()).await?;
+
+    let members = sqlx::query_as::<_, BudgetMemberWithUser>(
+        "SELECT bm.id, bm.budget_id, bm.user_id, bm.role, bm.created_at,
+            u.name as user_name, u.email as user_email, u.avatar as user_avatar
+         FROM budget_members bm
+         JOIN users u ON bm.user_id = u.id
+         WHERE bm.budget_id = ?"
+    )
+        .bind(budget_id)
+        .fetch_all(pool)
+        .await?;
+
+    let subject = format!("Your {cadence} spending report ({} to {})", report.period_start, report.period_end);
+
+    for member in members {
+        mail::enqueue_html(&member.user_email, &subject, &report.text, &report.html);
+    }
+
+    Ok(())
+}
+
+/// Sends a report for every budget whose `next_send_at` has passed, then
+/// advances it to [`next_send_at`] of `now`. Intended to run on an hourly
+/// scheduler tick (see `main.rs`), matching `recurring::run_due`'s cadence.
+///
+/// Returns the number of budgets a report was sent for.
+pub async fn run_due(pool: &DbPool, now: chrono::DateTime<Utc>) -> Result<usize, sqlx::Error> {
+    let due: Vec<(String, String)> = sqlx::query_as(
+        "SELECT budget_id, cadence FROM budget_report_settings
+         WHERE enabled = 1 AND next_send_at IS NOT NULL AND next_send_at <= ?"
+    )
+        .bind(now.to_rfc3339())
+        .fetch_all(pool)
+        .await?;
+
+    let mut sent = 0;
+
+    for (budget_id, cadence) in due {
+        if let Err(e) = send_report(pool, &budget_id, &cadence).await {
+            tracing::error!("Failed to send report for budget {}: {:?}", budget_id, e);
+            continue;
+        }
+
+        sqlx::query("UPDATE budget_report_settings SET next_send_at = ? WHERE budget_id = ?")
+            .bind(next_send_at(&cadence, now).to_rfc3339())
+            .bind(&budget_id)
+            .execute(pool)
+            .await?;
+
+        sent += 1;
+    }
+
+    Ok(sent)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn period_bounds_weekly_is_the_trailing_seven_days() {
+        let (start, end) = period_bounds("weekly", date(2024, 6, 10));
+
+        assert_eq!(start, date(2024, 6, 4));
+        assert_eq!(end, date(2024, 6, 10));
+    }
+
+    #[test]
+    fn period_bounds_monthly_is_month_to_date() {
+        let (start, end) = period_bounds("monthly", date(2024, 6, 10));
+
+        assert_eq!(start, date(2024, 6, 1));
+        assert_eq!(end, date(2024, 6, 10));
+    }
+
+    #[test]
+    fn next_send_at_weekly_adds_seven_days() {
+        let from = date(2024, 6, 10).and_hms_opt(9, 0, 0).unwrap().and_utc();
+
+        assert_eq!(next_send_at("weekly", from), from + Duration::days(7));
+    }
+
+    #[test]
+    fn next_send_at_monthly_rolls_over_into_next_year() {
+        let from = date(2024, 12, 15).and_hms_opt(9, 0, 0).unwrap().and_utc();
+
+        let next = next_send_at("monthly", from);
+
+        assert_eq!(next.date_naive(), date(2025, 1, 1));
+        assert_eq!(next.time(), from.time());
+    }
+
+    #[test]
+    fn html_escape_neutralizes_markup() {
+        assert_eq!(html_escape("<script>alert(1)</script>"), "&lt;script&gt;alert(1)&lt;/script&gt;");
+        assert_eq!(html_escape("Tom & Jerry's \"Budget\""), "Tom &amp; Jerry&#39;s &quot;Budget&quot;");
+    }
+}
@@ -11,17 +11,32 @@
 //! ## Authentication
 //! - `POST /api/auth/register` - User registration
 //! - `POST /api/auth/login` - User login
+//! - `POST /api/auth/refresh` - Exchange a refresh token for a new token pair
+//! - `POST /api/auth/logout` - Revoke a refresh token
 //!
 //! ## Budgets
 //! - `GET /api/budgets` - List user's budgets
 //! - `POST /api/budgets` - Create a new budget
 //! - `GET /api/budgets/:id` - Get a specific budget
 //! - `DELETE /api/budgets/:id` - Delete a budget
+//! - `POST /api/budgets/:id/share` - Create a read-only share link
+//! - `GET /api/shared/:token` - View a budget snapshot via a share link
 //!
 //! ## Budget Members
 //! - `GET /api/budgets/:budget_id/members` - List budget members
 //! - `POST /api/budgets/:budget_id/members` - Invite a member
 //! - `DELETE /api/budgets/:budget_id/members/:member_id` - Remove a member
+//! - `GET /api/budgets/:budget_id/events` - Subscribe to live budget events (SSE)
+//!
+//! ## Budget Alerts
+//! - `GET /api/budgets/:budget_id/alerts` - List spending thresholds
+//! - `POST /api/budgets/:budget_id/alerts` - Create a spending threshold
+//! - `DELETE /api/budgets/:budget_id/alerts/:alert_id` - Delete a spending threshold
+//! - `GET /api/budgets/:budget_id/alerts/status` - List currently-triggered thresholds
+//!
+//! ## Reports
+//! - `PUT /api/budgets/:budget_id/report-settings` - Enable/disable and pick cadence
+//! - `POST /api/budgets/:budget_id/report/preview` - Render the current period's report
 //!
 //! ## Invitations
 //! - `GET /api/invitations` - List pending invitations
@@ -37,42 +52,55 @@
 //! ## Transactions
 //! - `GET /api/budgets/:budget_id/transactions` - List transactions
 //! - `POST /api/budgets/:budget_id/transactions` - Create a transaction
+//! - `POST /api/budgets/:budget_id/transactions/bulk` - Import a batch of transactions
 //! - `DELETE /api/transactions/:id` - Delete a transaction
 //!
+//! ## Attachments
+//! - `POST /api/transactions/:id/attachments` - Upload a receipt
+//! - `GET /api/transactions/:id/attachments` - List a transaction's receipts
+//! - `DELETE /api/attachments/:id` - Delete a receipt
+//!
 //! ## Recurring Transactions
 //! - `GET /api/budgets/:budget_id/recurring` - List recurring transactions
 //! - `POST /api/budgets/:budget_id/recurring` - Create a recurring transaction
+//! - `GET /api/budgets/:budget_id/recurring/upcoming` - Preview future occurrences
 //! - `PUT /api/recurring/:id/toggle` - Toggle recurring transaction status
 //! - `DELETE /api/recurring/:id` - Delete a recurring transaction
+//! - `POST /api/recurring/run` - Force-generate due recurring transactions (admin)
+//! - `POST /api/recurring/:id/run` - Force-generate a single template's transactions
 
 use axum::{
     routing::{get, post, put, delete},
     Router,
 };
-use std::sync::Arc;
 use utoipa::OpenApi;
 use utoipa_swagger_ui::SwaggerUi;
-use crate::{handlers, DbPool};
+use crate::{handlers, AppState};
 use crate::openapi::ApiDoc;
 
 /// Creates and configures the main application router.
 ///
 /// # Arguments
 ///
-/// * `pool` - Shared database connection pool
+/// * `state` - Shared application state (database pool, event registry, and
+///   attachment storage)
 ///
 /// # Returns
 ///
 /// A configured `Router` with all API routes and shared state.
-pub fn create_router(pool: Arc<DbPool>) -> Router {
+pub fn create_router(state: AppState) -> Router {
     Router::new()
         // Auth routes
         .route("/api/auth/register", post(handlers::register))
         .route("/api/auth/login", post(handlers::login))
+        .route("/api/auth/refresh", post(handlers::refresh))
+        .route("/api/auth/logout", post(handlers::logout))
 
         // Budget routes
         .route("/api/budgets", get(handlers::get_budgets).post(handlers::create_budget))
         .route("/api/budgets/:id", get(handlers::get_budget).delete(handlers::delete_budget))
+        .route("/api/budgets/:id/share", post(handlers::share_budget))
+        .route("/api/shared/:token", get(handlers::get_shared_budget))
 
         // Budget members routes
         .route("/api/budgets/:budget_id/members",
@@ -80,6 +108,18 @@ pub fn create_router(pool: Arc<DbPool>) -> Router {
                    .post(handlers::budget_members::invite_member))
         .route("/api/budgets/:budget_id/members/:member_id",
                delete(handlers::budget_members::remove_member))
+        .route("/api/budgets/:budget_id/events", get(handlers::events::budget_events))
+
+        // Budget alert routes
+        .route("/api/budgets/:budget_id/alerts",
+               get(handlers::budget_alerts::get_alerts)
+                   .post(handlers::budget_alerts::create_alert))
+        .route("/api/budgets/:budget_id/alerts/status", get(handlers::budget_alerts::get_alert_status))
+        .route("/api/budgets/:budget_id/alerts/:alert_id", delete(handlers::budget_alerts::delete_alert))
+
+        // Report routes
+        .route("/api/budgets/:budget_id/report-settings", put(handlers::reports::update_report_settings))
+        .route("/api/budgets/:budget_id/report/preview", post(handlers::reports::preview_report))
 
         // Invitations routes
         .route("/api/invitations", get(handlers::invitations::get_my_invitations))
@@ -98,17 +138,25 @@ pub fn create_router(pool: Arc<DbPool>) -> Router {
         .route("/api/budgets/:budget_id/transactions",
                get(handlers::get_transactions)
                    .post(handlers::create_transaction))
+        .route("/api/budgets/:budget_id/transactions/bulk", post(handlers::bulk_import_transactions))
         .route("/api/transactions/:id", delete(handlers::delete_transaction))
+        .route("/api/transactions/:id/attachments",
+               get(handlers::attachments::get_attachments)
+                   .post(handlers::attachments::upload_attachment))
+        .route("/api/attachments/:id", delete(handlers::attachments::delete_attachment))
 
         // Recurring transaction routes
         .route("/api/budgets/:budget_id/recurring",
                get(handlers::get_recurring_transactions)
                    .post(handlers::create_recurring_transaction))
+        .route("/api/budgets/:budget_id/recurring/upcoming", get(handlers::get_upcoming_recurring))
         .route("/api/recurring/:id/toggle", put(handlers::toggle_recurring_transaction))
         .route("/api/recurring/:id", delete(handlers::delete_recurring_transaction))
+        .route("/api/recurring/run", post(handlers::run_recurring_transactions))
+        .route("/api/recurring/:id/run", post(handlers::run_recurring_transaction))
 
         // Swagger UI documentation
         .merge(SwaggerUi::new("/swagger-ui").url("/api-docs/openapi.json", ApiDoc::openapi()))
 
-        .with_state(pool)
+        .with_state(state)
 }
\ No newline at end of file
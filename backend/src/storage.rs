@@ -0,0 +1,185 @@
+// Copyright (c) 2024 Tempo Budget
+// SPDX-License-Identifier: MIT
+//
+// Receipt attachment storage backends.
+
+//! # Storage Module
+//!
+//! Receipt attachments need somewhere to live: self-hosters typically want
+//! plain files on disk, while cloud deployments want them pushed to object
+//! storage. [`Storage`] abstracts over that choice so `handlers::attachments`
+//! doesn't need to know which backend is configured; [`from_config`]
+//! picks [`LocalFsStorage`] or [`S3Storage`] based on `config.toml`'s
+//! `[storage]` section.
+
+use async_trait::async_trait;
+
+use crate::config::StorageConfig;
+
+/// Stores and retrieves attachment file content by an opaque key.
+///
+/// Implemented by [`LocalFsStorage`] (files on disk) and [`S3Storage`] (an
+/// S3-compatible object store). Handlers generate the key (an opaque
+/// `transaction_id/attachment_id` pair, never derived from client-supplied
+/// bytes like the upload filename) and persist it alongside the
+/// attachment's metadata row; they never need to know which backend
+/// produced or will serve it.
+#[async_trait]
+pub trait Storage: Send + Sync {
+    /// Writes `content` under `key`, creating or overwriting it.
+    async fn put(&self, key: &str, content_type: &str, content: Vec<u8>) -> Result<(), String>;
+
+    /// Reads back the content previously stored under `key`.
+    async fn get(&self, key: &str) -> Result<Vec<u8>, String>;
+
+    /// Removes the object stored under `key`. Succeeds if the key never
+    /// existed, so callers can delete without first checking for presence.
+    async fn delete(&self, key: &str) -> Result<(), String>;
+}
+
+/// Stores attachments as plain files under a configured directory on disk.
+/// The default backend, since it requires no external service.
+pub struct LocalFsStorage {
+    base_dir: std::path::PathBuf,
+}
+
+impl LocalFsStorage {
+    pub fn new(base_dir: impl Into<std::path::PathBuf>) -> Self {
+        Self { base_dir: base_dir.into() }
+    }
+
+    /// Resolves `key` to a path under `base_dir`, rejecting any key that
+    /// could escape it (an empty, `.` or `..` component, which also covers a
+    /// leading or doubled separator). `/` and `\` are allowed between
+    /// components, so a key may name a subdirectory. Keys are meant to be opaque identifiers we generated
+    /// ourselves, never client-supplied bytes, but we still refuse to walk
+    /// off `base_dir` in case that invariant is ever violated upstream.
+    fn path_for(&self, key: &str) -> Result<std::path::PathBuf, String> {
+        let is_safe_component = |c: &str| !c.is_empty() && c != "." && c != "..";
+        if !key.split(['/', '\\']).all(is_safe_component) {
+            return Err(format!("refusing unsafe storage key: {key}"));
+        }
+
+        Ok(self.base_dir.join(key))
+    }
+}
+
+#[async_trait]
+impl Storage for LocalFsStorage {
+    async fn put(&self, key: &str, _content_type: &str, content: Vec<u8>) -> Result<(), String> {
+        let path = self.path_for(key)?;
+        let dir = path.parent().unwrap_or(&self.base_dir);
+        tokio::fs::create_dir_all(dir)
+            .await
+            .map_err(|e| format!("failed to create storage directory: {e}"))?;
+
+        tokio::fs::write(&path, content)
+            .await
+            .map_err(|e| format!("failed to write attachment {key}: {e}"))
+    }
+
+    async fn get(&self, key: &str) -> Result<Vec<u8>, String> {
+        tokio::fs::read(self.path_for(key)?)
+            .await
+            .map_err(|e| format!("failed to read attachment {key}: {e}"))
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), String> {
+        match tokio::fs::remove_file(self.path_for(key)?).await {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(format!("failed to delete attachment {key}: {e}")),
+        }
+    }
+}
+
+/// Stores attachments in an S3-compatible bucket, for deployments that want
+/// receipts off the application host (e.g. behind a CDN or retention
+/// policy managed by the object store).
+pub struct S3Storage {
+    bucket: String,
+    client: aws_sdk_s3::Client,
+}
+
+impl S3Storage {
+    /// Builds a client pointed at `endpoint` (an S3-compatible endpoint URL,
+    /// e.g. a MinIO instance) using static credentials rather than the AWS
+    /// default provider chain, since self-hosters running third-party object
+    /// stores won't have `~/.aws` or instance-role credentials available.
+    pub fn new(endpoint: String, bucket: String, region: String, access_key: String, secret_key: String) -> Self {
+        let credentials = aws_sdk_s3::config::Credentials::new(access_key, secret_key, None, None, "config");
+
+        let config = aws_sdk_s3::Config::builder()
+            .endpoint_url(endpoint)
+            .region(aws_sdk_s3::config::Region::new(region))
+            .credentials_provider(credentials)
+            .force_path_style(true)
+            .build();
+
+        Self { bucket, client: aws_sdk_s3::Client::from_conf(config) }
+    }
+}
+
+#[async_trait]
+impl Storage for S3Storage {
+    async fn put(&self, key: &str, content_type: &str, content: Vec<u8>) -> Result<(), String> {
+        self.client
+            .put_object()
+            .bucket(&self.bucket)
+            .key(key)
+            .content_type(content_type)
+            .body(content.into())
+            .send()
+            .await
+            .map_err(|e| format!("failed to upload attachment {key} to S3: {e}"))?;
+
+        Ok(())
+    }
+
+    async fn get(&self, key: &str) -> Result<Vec<u8>, String> {
+        let object = self
+            .client
+            .get_object()
+            .bucket(&self.bucket)
+            .key(key)
+            .send()
+            .await
+            .map_err(|e| format!("failed to fetch attachment {key} from S3: {e}"))?;
+
+        object
+            .body
+            .collect()
+            .await
+            .map(|bytes| bytes.into_bytes().to_vec())
+            .map_err(|e| format!("failed to read attachment {key} response body: {e}"))
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), String> {
+        self.client
+            .delete_object()
+            .bucket(&self.bucket)
+            .key(key)
+            .send()
+            .await
+            .map_err(|e| format!("failed to delete attachment {key} from S3: {e}"))?;
+
+        Ok(())
+    }
+}
+
+/// Builds the configured `Storage` backend. Panics at startup (like
+/// `config::load`) if `backend` names an S3 store but a required field
+/// (`endpoint`, `bucket`, `access_key`, `secret_key`) is missing, since
+/// there's no sane runtime fallback for half-configured object storage.
+pub fn from_config(config: &StorageConfig) -> Box<dyn Storage> {
+    match config.backend.as_str() {
+        "s3" => Box::new(S3Storage::new(
+            config.s3_endpoint.clone().expect("storage.s3_endpoint is required when storage.backend = \"s3\""),
+            config.s3_bucket.clone().expect("storage.s3_bucket is required when storage.backend = \"s3\""),
+            config.s3_region.clone().unwrap_or_else(|| "us-east-1".to_string()),
+            config.s3_access_key.clone().expect("storage.s3_access_key is required when storage.backend = \"s3\""),
+            config.s3_secret_key.clone().expect("storage.s3_secret_key is required when storage.backend = \"s3\""),
+        )),
+        _ => Box::new(LocalFsStorage::new(config.local_dir.clone())),
+    }
+}